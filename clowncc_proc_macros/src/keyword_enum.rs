@@ -63,7 +63,7 @@ fn check_no_duplicates(lit_strs: &[&LitStr]) -> syn::Result<()> {
     let mut errors = ErrorsBuilder::new();
     for &ls in lit_strs {
         let string = ls.value();
-        if string == "" {
+        if string.is_empty() {
             errors.emplace(ls, "empty string not allowed in KeywordEnum");
         }
         match str_to_lit.entry(string) {
@@ -89,7 +89,7 @@ pub(crate) fn keyword_enum(structure: Structure) -> syn::Result<TokenStream> {
         Data::Enum(de) => de,
     };
 
-    let strings = collect_keywords(&data_enum)?;
+    let strings = collect_keywords(data_enum)?;
     check_no_duplicates(&strings)?;
 
     let map_elms: TokenStream = structure
@@ -1,85 +1,47 @@
 mod var_attribute;
 
-use var_attribute::VarAttribute;
+use var_attribute::{VarAttribute, LANGS};
 
 use clownlib_proc_macro_support::errors::ErrorsBuilder;
 
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::Error;
 use synstructure::Structure;
 
-fn gen_std_version_condition(
-    langs: TokenStream,
-    sinces: TokenStream,
-    untils: TokenStream,
-) -> TokenStream {
-    quote! {
-        const __langs: &[::clowncc_version::Language] = &[#langs];
-        const __sinces: &[::clowncc_version::StdVersion] = &[#sinces];
-        const __untils: &[::clowncc_version::StdVersion] = &[#untils];
-        let __lang = __langs.iter().filter(|&&l| l == __svlang).next();
-        let __since = __sinces.iter().filter(|&&sv| *sv == __svlang).next();
-        let __until = __untils.iter().filter(|&&sv| *sv == __svlang).next();
-        match (__since, __until) {
-            (None, None) => __lang.is_some(),
-            (Some(&ssv), None) => sv.is_since(ssv),
-            (None, Some(&usv)) => sv.is_before(usv),
-            (Some(&ssv), Some(&usv)) => sv.is_since(ssv) && sv.is_before(usv),
-        }
-    }
-}
-
-fn gen_language_condition(
-    langs: TokenStream,
-    sinces: TokenStream,
-    untils: TokenStream,
+/// Generates the arms matching each variant to a value for each language.
+fn generate_body(
+    var_attrs: &[VarAttribute],
+    value: impl Fn(&VarAttribute, &'static str) -> TokenStream,
 ) -> TokenStream {
-    quote! {
-        const __langs: &[::clowncc_version::Language] = &[#langs];
-        const __sinces: &[::clowncc_version::StdVersion] = &[#sinces];
-        const __untils: &[::clowncc_version::StdVersion] = &[#untils];
-        let __lang = __langs.iter().filter(|&&l| l == lang).next();
-        let __since = __sinces.iter().filter(|&&sv| *sv == lang).next();
-        let __until = __untils.iter().filter(|&&sv| *sv == lang).next();
-        __lang.is_some() || __since.is_some() || __until.is_some()
+    let mut stream = TokenStream::new();
+    for va in var_attrs {
+        let pat = va.var_info.pat();
+        let arms = LANGS.into_iter().map(|lang| {
+            let value = value(va, lang);
+            let lang = format_ident!("{}", lang);
+            quote!(::clowncc_version::Language::#lang => #value,)
+        });
+        stream.extend(quote!(#pat => match lang { #(#arms)* },));
     }
+    stream
 }
 
-fn generate_body(
+/// Generates the arms matching each variant to the version of each language
+/// picked from its range.
+fn generate_version_body(
     var_attrs: &[VarAttribute],
-    generate: impl Fn(TokenStream, TokenStream, TokenStream) -> TokenStream,
+    pick: fn((&'static str, &'static str)) -> &'static str,
 ) -> TokenStream {
-    let mut stream = TokenStream::new();
-    for va @ VarAttribute {
-        var_info,
-        langs,
-        sinces,
-        untils,
-    } in var_attrs
-    {
-        let pat = var_info.pat();
-        if va.is_universal() {
-            stream.extend(quote!(#pat => { true }));
-            continue;
+    generate_body(var_attrs, |va, lang| match va.version_range(lang) {
+        Some(range) => {
+            let sv = format_ident!("{}", pick(range));
+            quote!(::core::option::Option::Some(
+                ::clowncc_version::StdVersion::#sv
+            ))
         }
-        let langs = langs
-            .iter()
-            .map(|lang| quote!(::clowncc_version::Language::#lang,))
-            .collect();
-        let sinces = sinces
-            .iter()
-            .map(|since| quote!(::clowncc_version::StdVersion::#since,))
-            .collect();
-        let untils = untils
-            .iter()
-            .map(|until| quote!(::clowncc_version::StdVersion::#until,))
-            .collect();
-
-        let body = generate(langs, sinces, untils);
-        stream.extend(quote!(#pat => { #body }));
-    }
-    stream
+        None => quote!(::core::option::Option::None),
+    })
 }
 
 pub(crate) fn versioned(definition: Structure) -> syn::Result<TokenStream> {
@@ -103,18 +65,92 @@ pub(crate) fn versioned(definition: Structure) -> syn::Result<TokenStream> {
 
     errors.collect()?;
 
-    let std_version_body = generate_body(&var_attrs, gen_std_version_condition);
-    let language_body = generate_body(&var_attrs, gen_language_condition);
+    let first_body = generate_version_body(&var_attrs, |(first, _)| first);
+    let last_body = generate_version_body(&var_attrs, |(_, last)| last);
+    let language_body = generate_body(&var_attrs, |va, lang| {
+        let mentions = va.mentions_language(lang);
+        quote!(#mentions)
+    });
+
+    let name = &definition.ast().ident;
+    let (impl_generics, ty_generics, where_clause) =
+        definition.ast().generics.split_for_impl();
+
+    // Traits cannot have const functions, so the const equivalents of the
+    // trait methods are inherent. The `const_` prefix keeps them from
+    // clashing with methods of the type, which take precedence over the
+    // trait methods otherwise.
+    let inherent = quote! {
+        #[allow(dead_code)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Const equivalent of `StdVersionSupported::first_version`.
+            pub const fn const_first_version(
+                &self,
+                lang: ::clowncc_version::Language,
+            ) -> ::core::option::Option<::clowncc_version::StdVersion> {
+                match self { #first_body }
+            }
+
+            /// Const equivalent of `StdVersionSupported::last_version`.
+            pub const fn const_last_version(
+                &self,
+                lang: ::clowncc_version::Language,
+            ) -> ::core::option::Option<::clowncc_version::StdVersion> {
+                match self { #last_body }
+            }
 
-    Ok(definition.gen_impl(quote! {
+            /// Const equivalent of `StdVersionSupported::is_in_std_version`.
+            pub const fn const_is_in_std_version(
+                &self,
+                sv: ::clowncc_version::StdVersion,
+            ) -> bool {
+                let lang = sv.as_language();
+                match (
+                    self.const_first_version(lang),
+                    self.const_last_version(lang),
+                ) {
+                    (
+                        ::core::option::Option::Some(first),
+                        ::core::option::Option::Some(last),
+                    ) => sv.is_since(first) && last.is_since(sv),
+                    _ => false,
+                }
+            }
+
+            /// Const equivalent of `LanguageSupported::is_in_language`,
+            /// which holds for any language named by the `versioned`
+            /// attribute, even if only by a range with no versions.
+            pub const fn const_is_in_language(
+                &self,
+                lang: ::clowncc_version::Language,
+            ) -> bool {
+                match self { #language_body }
+            }
+        }
+    };
+
+    let impls = definition.gen_impl(quote! {
         extern crate clowncc_version;
         gen impl ::clowncc_version::StdVersionSupported for @Self {
             fn is_in_std_version(
                 &self,
                 sv: ::clowncc_version::StdVersion
             ) -> bool {
-                let __svlang = sv.as_language();
-                match self { #std_version_body }
+                Self::const_is_in_std_version(self, sv)
+            }
+
+            fn first_version(
+                &self,
+                lang: ::clowncc_version::Language
+            ) -> ::core::option::Option<::clowncc_version::StdVersion> {
+                Self::const_first_version(self, lang)
+            }
+
+            fn last_version(
+                &self,
+                lang: ::clowncc_version::Language
+            ) -> ::core::option::Option<::clowncc_version::StdVersion> {
+                Self::const_last_version(self, lang)
             }
         }
 
@@ -123,8 +159,10 @@ pub(crate) fn versioned(definition: Structure) -> syn::Result<TokenStream> {
                 &self,
                 lang: ::clowncc_version::Language
             ) -> bool {
-                match self { #language_body }
+                Self::const_is_in_language(self, lang)
             }
         }
-    }))
+    });
+
+    Ok(quote!(#inherent #impls))
 }
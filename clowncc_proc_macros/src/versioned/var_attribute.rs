@@ -9,10 +9,10 @@ use syn::{
 };
 use synstructure::VariantInfo;
 
-const LANGS: [&str; 2] = ["C", "Cpp"];
-const STD_VERSIONS: [&str; 12] = [
-    "C89", "C95", "C99", "C11", "C17", "C23", "Cpp11", "Cpp14", "Cpp17",
-    "Cpp20", "Cpp23", "Cpp26",
+pub(super) const LANGS: [&str; 2] = ["C", "Cpp"];
+const STD_VERSIONS: [&str; 14] = [
+    "C89", "C95", "C99", "C11", "C17", "C23", "C2y", "Cpp98", "Cpp11", "Cpp14",
+    "Cpp17", "Cpp20", "Cpp23", "Cpp26",
 ];
 
 #[test]
@@ -37,6 +37,15 @@ fn matches_version_package() {
     assert_eq!(version_strings, macro_strings);
 }
 
+/// Whether a language or version like `Cpp17` belongs to a language like
+/// `Cpp`, where `C` does not own `Cpp`.
+fn is_of_language(name: &str, lang: &str) -> bool {
+    match name.trim_start_matches(lang).chars().next() {
+        Some(c) => c.is_ascii_digit(),
+        None => true,
+    }
+}
+
 fn verify_impl(id: Ident, ty: &str, supported: &[&str]) -> syn::Result<Ident> {
     let name = id.to_string();
     if name.starts_with("C++") {
@@ -86,6 +95,43 @@ impl VarAttribute<'_> {
             .cloned()
             .all(Vec::is_empty)
     }
+
+    /// Whether the variant names `lang` at all, even if only in a range with
+    /// no versions like `until C89`.
+    pub(super) fn mentions_language(&self, lang: &str) -> bool {
+        let is_of_lang = |id: &Ident| is_of_language(&id.to_string(), lang);
+        self.is_universal()
+            || self.langs.iter().any(|l| l == lang)
+            || self.sinces.iter().chain(&self.untils).any(is_of_lang)
+    }
+
+    /// The first and last version of `lang` which the variant is in, where
+    /// `until` is exclusive so `until C89` leaves no versions.
+    pub(super) fn version_range(
+        &self,
+        lang: &str,
+    ) -> Option<(&'static str, &'static str)> {
+        let versions: Vec<_> = STD_VERSIONS
+            .into_iter()
+            .filter(|sv| is_of_language(sv, lang))
+            .collect();
+        let position = |ids: &[Ident]| {
+            ids.iter()
+                .find_map(|id| versions.iter().position(|&sv| id == sv))
+        };
+        let (start, end) =
+            if self.is_universal() || self.langs.iter().any(|l| l == lang) {
+                (0, versions.len())
+            } else {
+                match (position(&self.sinces), position(&self.untils)) {
+                    (None, None) => return None,
+                    (since, until) => {
+                        (since.unwrap_or(0), until.unwrap_or(versions.len()))
+                    }
+                }
+            };
+        (start < end).then(|| (versions[start], versions[end - 1]))
+    }
 }
 
 #[derive(Clone)]
@@ -160,13 +206,8 @@ fn check_duplication(
         let mut first_since = None;
         let mut first_until = None;
         for item in item_iter.iter().filter(|item| {
-            item.language().map_or(false, |i| {
-                i.to_string()
-                    .trim_start_matches(l)
-                    .chars()
-                    .next()
-                    .map_or(true, |c| c.is_ascii_digit())
-            })
+            item.language()
+                .is_some_and(|i| is_of_language(&i.to_string(), l))
         }) {
             match item {
                 Item::Universal(_) => unreachable!("should have been filtered"),
@@ -178,9 +178,144 @@ fn test_sinces_and_untils() {
 #[test]
 fn test_struct() {
     #[derive(Versioned, Default)]
-    #[versioned(since C89, since Cpp11)]
+    #[versioned(since C89, since Cpp98)]
     struct S;
 
     StdVersion::iter().for_each(|sv| assert!(S.is_in_std_version(sv)));
     Language::iter().for_each(|l| assert!(S.is_in_language(l)));
 }
+
+#[test]
+fn test_traits() {
+    #[derive(Versioned)]
+    enum E {
+        #[versioned(lang C, since Cpp17)]
+        A,
+    }
+
+    fn is_in<T: StdVersionSupported + LanguageSupported>(
+        t: &T,
+        sv: StdVersion,
+    ) -> bool {
+        t.is_in_language(sv.as_language()) && t.is_in_std_version(sv)
+    }
+
+    StdVersion::iter().for_each(|sv| {
+        assert_eq!(is_in(&E::A, sv), E::A.is_in_std_version(sv))
+    });
+}
+
+#[test]
+fn test_version_range() {
+    #[derive(Versioned)]
+    enum E {
+        #[versioned(universal)]
+        A,
+        #[versioned(lang C, since Cpp11, until Cpp20)]
+        B(),
+        #[versioned(until C99)]
+        C { i: i32 },
+        #[versioned(until Cpp98)]
+        D,
+    }
+
+    assert_eq!(E::A.first_version(Language::C), Some(StdVersion::C89));
+    assert_eq!(E::A.last_version(Language::C), Some(StdVersion::C2y));
+    assert_eq!(E::A.first_version(Language::Cpp), Some(StdVersion::Cpp98));
+    assert_eq!(E::A.last_version(Language::Cpp), Some(StdVersion::Cpp26));
+    assert!(E::A.supported_versions().eq(StdVersion::iter()));
+
+    assert_eq!(E::B().first_version(Language::C), Some(StdVersion::C89));
+    assert_eq!(E::B().last_version(Language::C), Some(StdVersion::C2y));
+    assert_eq!(E::B().first_version(Language::Cpp), Some(StdVersion::Cpp11));
+    assert_eq!(E::B().last_version(Language::Cpp), Some(StdVersion::Cpp17));
+    assert!(E::B()
+        .supported_versions()
+        .eq(StdVersion::iter().filter(
+            |sv| sv.is_c() || (sv.is_since_cpp11() && sv.is_before_cpp20())
+        )));
+
+    let c = E::C { i: 3 };
+    assert_eq!(c.first_version(Language::C), Some(StdVersion::C89));
+    assert_eq!(c.last_version(Language::C), Some(StdVersion::C95));
+    assert_eq!(c.first_version(Language::Cpp), None);
+    assert_eq!(c.last_version(Language::Cpp), None);
+    assert!(c
+        .supported_versions()
+        .eq([StdVersion::C89, StdVersion::C95]));
+
+    // Nothing is before the earliest version, though the language is named
+    assert!(E::D.is_in_language(Language::Cpp));
+    assert!(!E::D.is_in_language(Language::C));
+    assert_eq!(E::D.first_version(Language::Cpp), None);
+    assert_eq!(E::D.supported_versions().count(), 0);
+}
+
+#[test]
+fn test_const() {
+    #[derive(Versioned)]
+    enum Keyword {
+        #[versioned(universal)]
+        Int,
+        #[versioned(since C23, since Cpp11)]
+        Constexpr,
+        #[versioned(since Cpp20)]
+        Constinit,
+    }
+
+    const KEYWORDS: [Keyword; 3] =
+        [Keyword::Int, Keyword::Constexpr, Keyword::Constinit];
+    const fn count_in(sv: StdVersion) -> usize {
+        let mut count = 0;
+        let mut idx = 0;
+        while idx < KEYWORDS.len() {
+            if KEYWORDS[idx].const_is_in_std_version(sv) {
+                count += 1;
+            }
+            idx += 1;
+        }
+        count
+    }
+    const CPP98: usize = count_in(StdVersion::Cpp98);
+    const CPP20: usize = count_in(StdVersion::Cpp20);
+    const C23: usize = count_in(StdVersion::C23);
+    assert_eq!((CPP98, CPP20, C23), (1, 3, 2));
+
+    const CONSTINIT: Option<StdVersion> =
+        Keyword::Constinit.const_first_version(Language::Cpp);
+    assert_eq!(CONSTINIT, Some(StdVersion::Cpp20));
+    const {
+        assert!(!Keyword::Constinit.const_is_in_language(Language::C));
+    }
+    const LAST_INT: Option<StdVersion> =
+        Keyword::Int.const_last_version(Language::C);
+    assert_eq!(LAST_INT, Some(StdVersion::C2y));
+}
+
+#[test]
+fn test_own_methods() {
+    #[derive(Versioned)]
+    enum E {
+        #[versioned(since Cpp11)]
+        A,
+    }
+
+    // Methods of the type named like the trait methods do not clash
+    impl E {
+        fn first_version(&self) -> u8 {
+            1
+        }
+
+        fn supported_versions(&self) -> &'static str {
+            "c++11 onwards"
+        }
+    }
+
+    assert_eq!(E::A.first_version(), 1);
+    assert_eq!(E::A.supported_versions(), "c++11 onwards");
+    assert_eq!(
+        StdVersionSupported::first_version(&E::A, Language::Cpp),
+        Some(StdVersion::Cpp11)
+    );
+    assert_eq!(StdVersionSupported::supported_versions(&E::A).count(), 6);
+}
@@ -0,0 +1,97 @@
+//! Throughput of [`Cursor`] tokenizing 4 MiB sources of code, comments and
+//! non-ASCII text.
+//!
+//! To measure a change to the cursor, save a baseline before the change and
+//! compare against it after:
+//!
+//! ```text
+//! cargo bench -p clowncc_token --bench cursor -- --save-baseline before
+//! cargo bench -p clowncc_token --bench cursor -- --baseline before
+//! ```
+//!
+//! The char-by-char `eat_while` which the ASCII fast path replaced predates
+//! this benchmark. To compare against it, check out the parent of the commit
+//! adding the fast path, copy in this file and the criterion parts of
+//! `Cargo.toml` from that commit, and save the baseline there.
+
+use clowncc_token::Cursor;
+
+use clowncc_version::StdVersion;
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkId, Criterion,
+    Throughput,
+};
+
+/// Size each source is repeated up to, in the range of a large amalgamated
+/// source file.
+const SOURCE_LEN: usize = 4 << 20;
+
+const CODE: &str = r#"
+/*
+** Look up a symbol in the table and return its index or -1 when the symbol
+** has not been declared yet.
+*/
+static int symbol_table_lookup(SymbolTable *pTable, const char *zName){
+  int iBucket = symbol_hash(zName) % pTable->nBucket;
+  Symbol *pSymbol;
+  for(pSymbol=pTable->apBucket[iBucket]; pSymbol; pSymbol=pSymbol->pNext){
+    if( strcmp(pSymbol->zName, zName)==0 ) return pSymbol->iIndex;
+  }
+  return -1; /* not found */
+}
+
+#define SYMBOL_FLAG_USED   0x0001  // set once referenced
+#define SYMBOL_FLAG_EXTERN 0x0002  // declared with extern
+"#;
+
+const COMMENTS: &str = r#"
+/*************************************************************************
+** The author disclaims copyright to this source code. In place of a legal
+** notice, here is a blessing:
+**
+**    May you do good and not evil.
+**    May you find forgiveness for yourself and forgive others.
+**    May you share freely, never taking more than you give.
+*************************************************************************/
+// Line comments that run for a while before reaching the end of the line.
+"#;
+
+const UNICODE: &str = "
+/* Ünïcödé cömmënts and identifiers mix the ASCII and char paths. */
+int größe = naïve_café(\"€\", 'é'); // ½
+";
+
+fn repeat_to_len(snippet: &str) -> String {
+    snippet.repeat(SOURCE_LEN / snippet.len())
+}
+
+fn tokenize(code: &str) -> usize {
+    let mut cursor = Cursor::new(code, StdVersion::Cpp26);
+    let mut count = 0;
+    while let Some(token) = cursor.next_token() {
+        black_box(token);
+        count += 1;
+    }
+    count
+}
+
+fn bench_cursor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cursor");
+    for (name, snippet) in
+        [("code", CODE), ("comments", COMMENTS), ("unicode", UNICODE)]
+    {
+        let code = repeat_to_len(snippet);
+        group.throughput(Throughput::Bytes(code.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("tokenize", name),
+            &code,
+            |b, code| {
+                b.iter(|| tokenize(black_box(code)));
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_cursor);
+criterion_main!(benches);
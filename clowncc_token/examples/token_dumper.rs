@@ -1,4 +1,5 @@
-use clowncc_token::{Cursor, TokenKind};
+use clowncc_token::{ContextTokens, Cursor, SourcePos, TokenBuffer};
+use clowncc_version::StdDialect;
 
 use std::{
     alloc::{GlobalAlloc, Layout, System},
@@ -6,13 +7,6 @@ use std::{
     sync::atomic::{AtomicBool, Ordering},
 };
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-enum ParseHeader {
-    None,
-    Pound,
-    Include,
-}
-
 fn main() {
     let file_name = std::env::args()
         .nth(1)
@@ -25,55 +19,39 @@ fn main() {
         std::fs::read_to_string(file_name).unwrap()
     };
 
-    let std_vers = std::env::args()
+    let dialect: StdDialect = std::env::args()
         .nth(2)
         .as_deref()
         .unwrap_or("c++26")
         .parse()
-        .expect("Unknown language");
+        .unwrap_or_else(|err| {
+            eprintln!("token_dumper: {err}");
+            std::process::exit(2)
+        });
 
-    unsafe { GLOBAL.disable() };
-    let mut cursor = Cursor::new(&code, std_vers);
-
-    let mut parse_header = ParseHeader::None;
-    let mut token_start = 0;
-    while let Some(token) = if parse_header == ParseHeader::Include {
-        cursor.next_token_header()
-    } else {
-        cursor.next_token()
-    } {
-        let len = token.length() as usize;
-        let code_slice = &code[token_start..token_start + len];
-        unsafe { GLOBAL.enable() };
-        println!("{:?} = (\"{}\")", token, code_slice);
-        unsafe { GLOBAL.disable() };
-        token_start += len;
-
-        match token.kind() {
-            TokenKind::BlockComment
-            | TokenKind::Whitespace {
-                splits_lines: false,
-            } => continue,
-            TokenKind::Pound if parse_header == ParseHeader::None => {
-                parse_header = ParseHeader::Pound;
-            }
-            TokenKind::Identifier {
-                has_univ_char: false,
-            } if matches!(
-                (parse_header, code_slice),
-                (ParseHeader::Pound, "include")
-            ) =>
-            {
-                parse_header = ParseHeader::Include;
-            }
-            _ => {
-                parse_header = ParseHeader::None;
-            }
-        }
+    GLOBAL.disable();
+    let mut tokens = ContextTokens::new(Cursor::new(&code, dialect));
+    let mut pos = SourcePos::START;
+    for (token, text) in tokens.by_ref() {
+        GLOBAL.enable();
+        println!("{:?} {:?} = (\"{}\")", pos, token, text);
+        GLOBAL.disable();
+        pos.advance(&token, text);
     }
-    unsafe { GLOBAL.enable() };
+    let token_start = tokens.offset() as usize;
+    GLOBAL.enable();
     println!("remaining = (\"{}\")", &code[token_start..]);
     assert_eq!(token_start, code.len());
+
+    // Once grown, the buffer can tokenize the file again without allocating
+    let mut buffer = TokenBuffer::new();
+    buffer.tokenize(Cursor::new(&code, dialect));
+    let token_count = buffer.len();
+    GLOBAL.disable();
+    buffer.tokenize(Cursor::new(&code, dialect));
+    GLOBAL.enable();
+    println!("buffered tokens = {token_count}");
+    assert_eq!(token_count, buffer.len());
 }
 
 struct ToggleAlloc(AtomicBool);
@@ -107,4 +85,4 @@ unsafe impl GlobalAlloc for ToggleAlloc {
 }
 
 #[global_allocator]
-static mut GLOBAL: ToggleAlloc = ToggleAlloc(AtomicBool::new(true));
+static GLOBAL: ToggleAlloc = ToggleAlloc(AtomicBool::new(true));
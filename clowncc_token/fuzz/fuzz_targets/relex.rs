@@ -0,0 +1,61 @@
+//! Checks that re-lexing a [`TokenBuffer`] after an arbitrary edit finds the
+//! same tokens as lexing the edited source from scratch, with arbitrary
+//! language options.
+//!
+//! Run with `cargo +nightly fuzz run relex` from `clowncc_token`.
+
+#![no_main]
+
+// Only shared for the language options
+#[allow(dead_code)]
+#[path = "../../tests/invariants/mod.rs"]
+mod invariants;
+
+use clowncc_token::{Cursor, Span, TextEdit, TokenBuffer};
+
+use clowncc_version::StdVersion;
+use libfuzzer_sys::fuzz_target;
+use strum::IntoEnumIterator;
+
+fuzz_target!(|input: (u8, &str, u16, u16, &str)| {
+    let (switches, code, start, len, new_text) = input;
+    let start = floor_char_boundary(code, usize::from(start));
+    let end = floor_char_boundary(code, start + usize::from(len));
+    let edited = [&code[..start], new_text, &code[end..]].concat();
+    let edit = TextEdit::new(
+        Span::new(start as u32, end as u32),
+        new_text.len() as u32,
+    );
+
+    for sv in StdVersion::iter() {
+        let lang_opts = invariants::lang_options(sv, switches);
+        let mut buffer = TokenBuffer::new();
+        buffer.tokenize(Cursor::new(code, lang_opts));
+        buffer.relex(Cursor::new(&edited, lang_opts), edit);
+
+        let mut expected = TokenBuffer::new();
+        expected.tokenize(Cursor::new(&edited, lang_opts));
+        assert_eq!(
+            buffer.kinds(),
+            expected.kinds(),
+            "{lang_opts:?} {edited:?}"
+        );
+        assert_eq!(
+            buffer.starts(),
+            expected.starts(),
+            "{lang_opts:?} {edited:?}"
+        );
+        assert_eq!(
+            buffer.lengths(),
+            expected.lengths(),
+            "{lang_opts:?} {edited:?}"
+        );
+    }
+});
+
+fn floor_char_boundary(code: &str, index: usize) -> usize {
+    (0..=index.min(code.len()))
+        .rev()
+        .find(|&idx| code.is_char_boundary(idx))
+        .unwrap_or(0)
+}
@@ -0,0 +1,23 @@
+//! Checks the invariants of tokenizing arbitrary source in every standard
+//! version, with arbitrary language options and both with and without
+//! header-names.
+//!
+//! Run with `cargo +nightly fuzz run tokenize` from `clowncc_token`.
+
+#![no_main]
+
+#[path = "../../tests/invariants/mod.rs"]
+mod invariants;
+
+use clowncc_version::StdVersion;
+use libfuzzer_sys::fuzz_target;
+use strum::IntoEnumIterator;
+
+fuzz_target!(|input: (u8, &str)| {
+    let (switches, code) = input;
+    for sv in StdVersion::iter() {
+        let lang_opts = invariants::lang_options(sv, switches);
+        invariants::check_tokens(code, lang_opts, false);
+        invariants::check_tokens(code, lang_opts, true);
+    }
+});
@@ -0,0 +1,249 @@
+//! Compares the tokens of two C or C++ sources while ignoring whitespace,
+//! comments and other differences in how the tokens are spelled.
+//!
+//! Usage: `token_diff [options] <old file> <new file> [dialect]`
+//!
+//! The dialect defaults to `c++26` and the options are `-ftrigraphs`,
+//! `-fno-digraphs` and `-fdollars-in-identifiers`.
+//!
+//! Prints each run of differing tokens and exits with status 1 if there were
+//! any, or 2 if the arguments or files could not be read.
+
+use clowncc_token::{
+    write_canonical, Cursor, InputCharset, InputDecoder, PosCursor, SourcePos,
+};
+
+use clowncc_version::{LangOptions, StdDialect};
+
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: token_diff [-ftrigraphs] [-fno-digraphs] \
+                     [-fdollars-in-identifiers] <old file> <new file> \
+                     [dialect]";
+
+struct DiffToken {
+    text: String,
+    pos: SourcePos,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Op {
+    Keep,
+    Delete,
+    Insert,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (flags, args): (Vec<_>, Vec<_>) = args
+        .iter()
+        .partition(|arg| arg.starts_with('-') && *arg != "-");
+    let [old_name, new_name, rest @ ..] = args.as_slice() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let dialect: StdDialect =
+        match rest.first().map_or("c++26", |arg| arg.as_str()).parse() {
+            Ok(dialect) => dialect,
+            Err(err) => {
+                eprintln!("token_diff: {err}");
+                return ExitCode::from(2);
+            }
+        };
+    let mut lang_opts = LangOptions::from(dialect);
+    for flag in flags {
+        lang_opts = match flag.as_str() {
+            "-ftrigraphs" => lang_opts.with_trigraphs(true),
+            "-fno-digraphs" => lang_opts.with_digraphs(false),
+            "-fdollars-in-identifiers" => {
+                lang_opts.with_dollars_in_identifiers(true)
+            }
+            _ => {
+                eprintln!("token_diff: unknown option '{flag}'\n{USAGE}");
+                return ExitCode::from(2);
+            }
+        };
+    }
+
+    let (old_bytes, new_bytes) = match (read(old_name), read(new_name)) {
+        (Ok(old_bytes), Ok(new_bytes)) => (old_bytes, new_bytes),
+        (Err(err), _) | (_, Err(err)) => {
+            eprintln!("token_diff: {err}");
+            return ExitCode::from(2);
+        }
+    };
+    let old_input = InputDecoder::new(InputCharset::Utf8).decode(&old_bytes);
+    let new_input = InputDecoder::new(InputCharset::Utf8).decode(&new_bytes);
+    let (old_code, new_code) = (old_input.text(), new_input.text());
+    let old = significant_tokens(old_code, lang_opts);
+    let new = significant_tokens(new_code, lang_opts);
+
+    let ops = diff(&old, &new);
+    let (mut old_idx, mut new_idx) = (0, 0);
+    let mut has_diff = false;
+    let mut ops = ops.as_slice();
+    while let Some((&op, rest)) = ops.split_first() {
+        if op == Op::Keep {
+            old_idx += 1;
+            new_idx += 1;
+            ops = rest;
+            continue;
+        }
+        has_diff = true;
+        let hunk_len = ops.iter().take_while(|&&op| op != Op::Keep).count();
+        let (hunk, rest) = ops.split_at(hunk_len);
+        println!(
+            "@@ {old_name}:{} {new_name}:{} @@",
+            pos_at(&old, old_idx, old_code),
+            pos_at(&new, new_idx, new_code),
+        );
+        for &op in hunk {
+            if op == Op::Delete {
+                println!("- {}", old[old_idx].text);
+                old_idx += 1;
+            }
+        }
+        for &op in hunk {
+            if op == Op::Insert {
+                println!("+ {}", new[new_idx].text);
+                new_idx += 1;
+            }
+        }
+        ops = rest;
+    }
+    ExitCode::from(u8::from(has_diff))
+}
+
+/// Reads the file at `path`, naming it in the error.
+fn read(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|err| format!("{path}: {err}"))
+}
+
+/// Every token other than whitespace and comments with its canonical
+/// spelling.
+fn significant_tokens(code: &str, lang_opts: LangOptions) -> Vec<DiffToken> {
+    let mut cursor = PosCursor::new(Cursor::new(code, lang_opts));
+    let mut tokens = Vec::new();
+    while let Some((token, pos)) = cursor.next_token() {
+        if token.kind().is_trivia() {
+            continue;
+        }
+        let start = pos.offset() as usize;
+        let source = &code[start..start + token.length() as usize];
+        let mut text = String::new();
+        write_canonical(&mut text, &token, source).unwrap();
+        tokens.push(DiffToken { text, pos });
+    }
+    tokens
+}
+
+/// Line and column of the token at `index`, or of the end of the source.
+fn pos_at(tokens: &[DiffToken], index: usize, code: &str) -> String {
+    match tokens.get(index) {
+        Some(token) => format!("{}:{}", token.pos.line(), token.pos.column()),
+        None => {
+            let line = code.lines().count().max(1);
+            format!("{line}:end")
+        }
+    }
+}
+
+/// Shortest edit script turning `old` into `new`.
+fn diff(old: &[DiffToken], new: &[DiffToken]) -> Vec<Op> {
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    diff_into(old, new, &mut ops);
+    ops
+}
+
+/// Appends the shortest edit script turning `old` into `new` to `ops` using
+/// the linear space variant of Myers' algorithm, which splits the sources at
+/// the middle snake of an optimal path and recurses on either side.
+fn diff_into(old: &[DiffToken], new: &[DiffToken], ops: &mut Vec<Op>) {
+    // Most edits are small so only search between the common ends
+    let same = |(a, b): (&DiffToken, &DiffToken)| a.text == b.text;
+    let prefix = old.iter().zip(new).take_while(|&p| same(p)).count();
+    let (old, new) = (&old[prefix..], &new[prefix..]);
+    let suffix = old.iter().rev().zip(new.iter().rev());
+    let suffix = suffix.take_while(|&p| same(p)).count();
+    let (old, new) = (&old[..old.len() - suffix], &new[..new.len() - suffix]);
+
+    ops.resize(ops.len() + prefix, Op::Keep);
+    if old.is_empty() || new.is_empty() {
+        ops.resize(ops.len() + old.len(), Op::Delete);
+        ops.resize(ops.len() + new.len(), Op::Insert);
+    } else {
+        // Without common ends at least two edits remain, so both halves are
+        // strictly smaller
+        let ((x, y), (u, v)) = middle_snake(old, new);
+        diff_into(&old[..x], &new[..y], ops);
+        ops.resize(ops.len() + (u - x), Op::Keep);
+        diff_into(&old[u..], &new[v..], ops);
+    }
+    ops.resize(ops.len() + suffix, Op::Keep);
+}
+
+/// Start and end of a snake, a run of kept tokens, as `(x, y)` positions in
+/// the old and new tokens.
+type Snake = ((usize, usize), (usize, usize));
+
+/// The snake in the middle of a shortest edit script, found by searching
+/// forward from the start and backward from the end at once.
+fn middle_snake(old: &[DiffToken], new: &[DiffToken]) -> Snake {
+    let snake = |((x, y), (u, v)): ((isize, isize), (isize, isize))| {
+        ((x as usize, y as usize), (u as usize, v as usize))
+    };
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = (n + m + 1) / 2;
+    let delta = n - m;
+    let is_odd = delta % 2 != 0;
+    let same =
+        |x: isize, y: isize| old[x as usize].text == new[y as usize].text;
+    let same_rev = |x: isize, y: isize| same(n - 1 - x, m - 1 - y);
+    // Furthest x reached on each diagonal k = x - y, indexed by k + offset,
+    // where the backward search measures x and y from the ends
+    let offset = max + 1;
+    let mut forward = vec![0isize; 2 * offset as usize + 1];
+    let mut backward = forward.clone();
+    let at = |k: isize| (k + offset) as usize;
+    let furthest = |v: &[isize], k: isize, d: isize| {
+        if k == -d || (k != d && v[at(k - 1)] < v[at(k + 1)]) {
+            v[at(k + 1)]
+        } else {
+            v[at(k - 1)] + 1
+        }
+    };
+
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let x0 = furthest(&forward, k, d);
+            let (mut x, mut y) = (x0, x0 - k);
+            while x < n && y < m && same(x, y) {
+                (x, y) = (x + 1, y + 1);
+            }
+            forward[at(k)] = x;
+            let back_k = delta - k;
+            if is_odd
+                && (-(d - 1)..=d - 1).contains(&back_k)
+                && x + backward[at(back_k)] >= n
+            {
+                return snake(((x0, x0 - k), (x, y)));
+            }
+        }
+        for k in (-d..=d).step_by(2) {
+            let x0 = furthest(&backward, k, d);
+            let (mut x, mut y) = (x0, x0 - k);
+            while x < n && y < m && same_rev(x, y) {
+                (x, y) = (x + 1, y + 1);
+            }
+            backward[at(k)] = x;
+            let forward_k = delta - k;
+            if !is_odd
+                && (-d..=d).contains(&forward_k)
+                && forward[at(forward_k)] + x >= n
+            {
+                return snake(((n - x, m - y), (n - x0, m - (x0 - k))));
+            }
+        }
+    }
+    unreachable!("the searches meet within (n + m) / 2 edits")
+}
@@ -5,6 +5,7 @@ mod internal {
 }
 
 impl internal::Sealed for char {}
+impl internal::Sealed for u32 {}
 
 pub trait CharInfo: internal::Sealed + Copy {
     fn is_id_start(self) -> bool;
@@ -74,12 +75,12 @@ impl CharInfo for char {
         if sv.is_c() && matches!(self, '\u{0A}' /*'\n'*/) {
             return true;
         }
-        if sv.is_cpp26()
+        if (sv.is_since_c23() || sv.is_since_cpp26())
             && matches!(
                 self,
-                '\u{7C}' // '|'
-                    | '\u{7D}' // '}'
-                    | '\u{7E}' // '~'
+                '\u{24}' // '$'
+                    | '\u{40}' // '@'
+                    | '\u{60}' // '`'
             )
         {
             return true;
@@ -97,8 +98,11 @@ impl CharInfo for char {
         ) || self.is_in_basic_set(sv)
     }
 
+    /// The translation character set of C++23 is every Unicode scalar value,
+    /// including control characters, which a `char` always is.
+    #[inline]
     fn is_in_translation_set(self) -> bool {
-        todo!()
+        true
     }
 
     fn is_c_char(self, sv: StdVersion) -> bool {
@@ -106,9 +110,14 @@ impl CharInfo for char {
     }
 
     fn is_d_char(self, sv: StdVersion) -> bool {
-        self.is_in_basic_set(sv) && !matches!(self, '(' | ')' | '\\' | ' ')
+        self.is_in_basic_set(sv)
+            && !matches!(
+                self,
+                '(' | ')' | '\\' | ' ' | '\t' | '\u{0B}' | '\u{0C}' | '\n'
+            )
     }
 
+    #[inline]
     fn is_r_char(self) -> bool {
         self.is_in_translation_set()
     }
@@ -117,3 +126,44 @@ impl CharInfo for char {
         self.is_in_basic_set(sv) && !matches!(self, '"' | '\\' | '\n')
     }
 }
+
+/// Code points, such as the value of a universal character name, which may
+/// not be a Unicode scalar value.
+impl CharInfo for u32 {
+    fn is_id_start(self) -> bool {
+        char::from_u32(self).is_some_and(char::is_id_start)
+    }
+
+    fn is_id_continue(self) -> bool {
+        char::from_u32(self).is_some_and(char::is_id_continue)
+    }
+
+    fn is_in_basic_set(self, sv: StdVersion) -> bool {
+        char::from_u32(self).is_some_and(|c| c.is_in_basic_set(sv))
+    }
+
+    fn is_in_basic_literal_set(self, sv: StdVersion) -> bool {
+        char::from_u32(self).is_some_and(|c| c.is_in_basic_literal_set(sv))
+    }
+
+    /// Surrogates and values past `U+10FFFF` are not scalar values.
+    fn is_in_translation_set(self) -> bool {
+        char::from_u32(self).is_some()
+    }
+
+    fn is_c_char(self, sv: StdVersion) -> bool {
+        char::from_u32(self).is_some_and(|c| c.is_c_char(sv))
+    }
+
+    fn is_d_char(self, sv: StdVersion) -> bool {
+        char::from_u32(self).is_some_and(|c| c.is_d_char(sv))
+    }
+
+    fn is_r_char(self) -> bool {
+        self.is_in_translation_set()
+    }
+
+    fn is_s_char(self, sv: StdVersion) -> bool {
+        char::from_u32(self).is_some_and(|c| c.is_s_char(sv))
+    }
+}
@@ -0,0 +1,221 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    printer::write_without_splices, ContextTokens, Cursor, Span, Token,
+    TokenKind,
+};
+
+/// What a comment documents, following the markers of Doxygen.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CommentStyle {
+    /// A `//` or `/* */` comment which documents nothing.
+    Plain,
+    /// A `///`, `//!`, `/**` or `/*!` comment which documents the next
+    /// significant token.
+    DocNext,
+    /// A `///<`, `//!<`, `/**<` or `/*!<` comment which documents the
+    /// previous significant token.
+    DocPrevious,
+}
+
+/// A line or block comment along with the token it documents.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Comment<'chars> {
+    text: &'chars str,
+    span: Span,
+    is_block: bool,
+    has_trigraph: bool,
+    style: CommentStyle,
+    documents: Option<Span>,
+}
+
+impl<'chars> Comment<'chars> {
+    fn new(token: &Token, text: &'chars str, span: Span) -> Comment<'chars> {
+        let is_block = token.kind() == TokenKind::BlockComment;
+        let mut comment = Comment {
+            text,
+            span,
+            is_block,
+            has_trigraph: token.flags().has_trigraph(),
+            style: CommentStyle::Plain,
+            documents: None,
+        };
+        comment.style = split_marker(&comment.unspliced(), is_block).0;
+        comment
+    }
+
+    /// The source text of the comment including its markers.
+    #[must_use]
+    pub const fn text(&self) -> &'chars str {
+        self.text
+    }
+
+    #[must_use]
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    #[must_use]
+    pub const fn is_block(&self) -> bool {
+        self.is_block
+    }
+
+    #[must_use]
+    pub const fn style(&self) -> CommentStyle {
+        self.style
+    }
+
+    #[must_use]
+    pub const fn is_doc(&self) -> bool {
+        !matches!(self.style, CommentStyle::Plain)
+    }
+
+    /// The span of the significant token this comment documents, if it is a
+    /// doc comment and there is such a token.
+    #[must_use]
+    pub const fn documents(&self) -> Option<Span> {
+        self.documents
+    }
+
+    /// Prints the text of the comment without its markers, line splices or
+    /// the `*` gutters of block comments.
+    ///
+    /// A single space after a marker or gutter is removed, as is indentation
+    /// common to the lines of a block comment without gutters and any blank
+    /// lines at the start or end.
+    pub fn write_contents(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let unspliced = self.unspliced();
+        let body = split_marker(&unspliced, self.is_block).1;
+        if !self.is_block {
+            return out.write_str(strip_space(body).trim_end());
+        }
+
+        let lines: Vec<&str> = body.lines().map(str::trim_end).collect();
+        let indent = lines
+            .iter()
+            .skip(1)
+            .filter(|line| !line.is_empty() && gutter(line).is_none())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let lines: Vec<&str> = lines
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| match gutter(line) {
+                Some(rest) => strip_space(rest),
+                None if idx == 0 => strip_space(line),
+                None => line.get(indent..).unwrap_or(line.trim_start()),
+            })
+            .collect();
+        let start = lines.iter().position(|line| !line.is_empty());
+        let end = lines.iter().rposition(|line| !line.is_empty());
+        let (Some(start), Some(end)) = (start, end) else {
+            return Ok(());
+        };
+        for (idx, line) in lines[start..=end].iter().enumerate() {
+            if idx > 0 {
+                out.write_char('\n')?;
+            }
+            out.write_str(line)?;
+        }
+        Ok(())
+    }
+
+    /// The text of the comment as printed by [`Comment::write_contents`].
+    #[must_use]
+    pub fn contents(&self) -> String {
+        let mut contents = String::new();
+        self.write_contents(&mut contents)
+            .expect("writing to a string cannot fail");
+        contents
+    }
+
+    fn unspliced(&self) -> Cow<'chars, str> {
+        if !self.text.contains('\\') && !self.has_trigraph {
+            return Cow::Borrowed(self.text);
+        }
+        let mut text = String::with_capacity(self.text.len());
+        write_without_splices(&mut text, self.text, self.has_trigraph)
+            .expect("writing to a string cannot fail");
+        Cow::Owned(text)
+    }
+}
+
+/// Splits the text of a comment, after line splices are removed, into its
+/// style and the body after its markers.
+fn split_marker(text: &str, is_block: bool) -> (CommentStyle, &str) {
+    let rest = &text[2..];
+    let body = match rest.strip_suffix("*/") {
+        Some(body) if is_block => body,
+        _ => rest,
+    };
+    // `////` and `/***` are decorations rather than doc comments
+    let doc_marker = if is_block { '*' } else { '/' };
+    let is_doc = match body.chars().next() {
+        Some('!') => true,
+        Some(c) if c == doc_marker => !body[1..].starts_with(doc_marker),
+        _ => false,
+    };
+    if !is_doc {
+        return (CommentStyle::Plain, body);
+    }
+    match body[1..].strip_prefix('<') {
+        Some(body) => (CommentStyle::DocPrevious, body),
+        None => (CommentStyle::DocNext, &body[1..]),
+    }
+}
+
+/// The rest of a line of a block comment after its `*` gutter.
+fn gutter(line: &str) -> Option<&str> {
+    line.trim_start().strip_prefix('*')
+}
+
+fn strip_space(text: &str) -> &str {
+    text.strip_prefix(' ').unwrap_or(text)
+}
+
+/// Collects every comment of the source along with the significant token
+/// each doc comment documents.
+///
+/// Consecutive doc comments all document the same token, so the contents of
+/// a run of `///` lines can be joined to find the whole documentation.
+#[must_use]
+pub fn collect_comments(cursor: Cursor<'_>) -> Vec<Comment<'_>> {
+    let mut comments: Vec<Comment<'_>> = Vec::new();
+    // Comments from here on are waiting for a significant token to follow
+    let mut waiting = 0;
+    let mut previous = None;
+    let mut tokens = ContextTokens::new(cursor);
+    loop {
+        let start = tokens.offset();
+        let Some((token, text)) = tokens.next() else {
+            break;
+        };
+        let span = Span::new(start, tokens.offset());
+        match token.kind() {
+            TokenKind::LineComment | TokenKind::BlockComment => {
+                let mut comment = Comment::new(&token, text, span);
+                if comment.style == CommentStyle::DocPrevious {
+                    comment.documents = previous;
+                }
+                comments.push(comment);
+            }
+            TokenKind::Whitespace { .. } => {}
+            kind => {
+                for comment in &mut comments[waiting..] {
+                    if comment.style == CommentStyle::DocNext {
+                        comment.documents = Some(span);
+                    }
+                }
+                waiting = comments.len();
+                // Trailing doc comments document the member before the
+                // separator, as in `int a; ///< The a`
+                if !matches!(kind, TokenKind::SemiColon | TokenKind::Comma) {
+                    previous = Some(span);
+                }
+            }
+        }
+    }
+    comments
+}
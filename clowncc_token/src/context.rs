@@ -0,0 +1,175 @@
+use core::{fmt, iter::FusedIterator};
+
+use crate::{write_canonical, Cursor, Token, TokenKind, Tokens};
+
+use clowncc_version::StdVersion;
+
+/// Where the previous tokens of the line leave the next token.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Context {
+    /// Only whitespace and comments are on the line so far.
+    LineStart,
+    /// After a `#` starting a line.
+    DirectiveName,
+    /// After `export` starting a line.
+    Export,
+    /// A header-name may come next, within a directive or not.
+    HeaderName { in_directive: bool },
+    /// After `__has_include` or `__has_embed` within a directive.
+    HasInclude,
+    /// Within the rest of a directive.
+    Directive,
+    /// Within a line which is not a directive.
+    Text,
+}
+
+/// Like [`Tokens`] but lexes header-names wherever the context of the line
+/// allows them, so `<` and `"` start a header-name after:
+///
+/// - `#include`, `#include_next` and `#import`, and `#embed` since C23 and
+///   C++26,
+/// - `__has_include(`, `__has_include_next(` and `__has_embed(` within a
+///   directive,
+/// - `import` and `export import` starting a line since C++20.
+pub struct ContextTokens<'chars> {
+    tokens: Tokens<'chars>,
+    std_vers: StdVersion,
+    context: Context,
+}
+
+impl<'chars> ContextTokens<'chars> {
+    /// The cursor is taken to be at the start of a line.
+    #[must_use]
+    pub fn new(cursor: Cursor<'chars>) -> ContextTokens<'chars> {
+        ContextTokens {
+            std_vers: cursor.std_vers(),
+            tokens: Tokens::new(cursor),
+            context: Context::LineStart,
+        }
+    }
+
+    /// The byte offset the next token will start at.
+    #[must_use]
+    pub const fn offset(&self) -> u32 {
+        self.tokens.offset()
+    }
+
+    /// Whether the next token is lexed as a header-name if it can be one.
+    #[must_use]
+    pub fn expects_header(&self) -> bool {
+        matches!(self.context, Context::HeaderName { .. })
+    }
+
+    fn has_embed(&self) -> bool {
+        self.std_vers.is_since_c23() || self.std_vers.is_since_cpp26()
+    }
+
+    fn has_modules(&self) -> bool {
+        self.std_vers.is_since_cpp20()
+    }
+
+    fn next_context(&self, token: &Token, text: &str) -> Context {
+        let is_ident = |name| is_identifier(token, text, name);
+        match (self.context, token.kind()) {
+            (_, TokenKind::Whitespace { splits_lines: true }) => {
+                Context::LineStart
+            }
+            (
+                context,
+                TokenKind::Whitespace { .. }
+                | TokenKind::LineComment
+                | TokenKind::BlockComment,
+            ) => context,
+            (Context::LineStart, TokenKind::Pound) => Context::DirectiveName,
+            (Context::LineStart, _)
+                if self.has_modules() && is_ident("import") =>
+            {
+                Context::HeaderName {
+                    in_directive: false,
+                }
+            }
+            (Context::LineStart, _)
+                if self.has_modules() && is_ident("export") =>
+            {
+                Context::Export
+            }
+            (Context::Export, _) if is_ident("import") => Context::HeaderName {
+                in_directive: false,
+            },
+            (Context::DirectiveName, _)
+                if is_ident("include")
+                    || is_ident("include_next")
+                    || is_ident("import")
+                    || (self.has_embed() && is_ident("embed")) =>
+            {
+                Context::HeaderName { in_directive: true }
+            }
+            (Context::Directive, _)
+                if is_ident("__has_include")
+                    || is_ident("__has_include_next")
+                    || (self.has_embed() && is_ident("__has_embed")) =>
+            {
+                Context::HasInclude
+            }
+            (Context::HasInclude, TokenKind::OpenParen) => {
+                Context::HeaderName { in_directive: true }
+            }
+            (
+                Context::DirectiveName
+                | Context::HasInclude
+                | Context::Directive
+                | Context::HeaderName { in_directive: true },
+                _,
+            ) => Context::Directive,
+            (
+                Context::LineStart
+                | Context::Export
+                | Context::Text
+                | Context::HeaderName {
+                    in_directive: false,
+                },
+                _,
+            ) => Context::Text,
+        }
+    }
+}
+
+impl<'chars> Iterator for ContextTokens<'chars> {
+    type Item = (Token, &'chars str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (token, text) = if self.expects_header() {
+            self.tokens.next_header()?
+        } else {
+            self.tokens.next()?
+        };
+        self.context = self.next_context(&token, text);
+        Some((token, text))
+    }
+}
+
+impl FusedIterator for ContextTokens<'_> {}
+
+/// Whether the token is the identifier `name`, even if spelled with line
+/// splices.
+fn is_identifier(token: &Token, text: &str, name: &str) -> bool {
+    if !matches!(token.kind(), TokenKind::Identifier { .. }) {
+        return false;
+    }
+    if !token.flags().has_new_line() {
+        return text == name;
+    }
+
+    /// Compares written text against the remaining expected text.
+    struct Matcher<'name>(&'name str);
+
+    impl fmt::Write for Matcher<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 = self.0.strip_prefix(s).ok_or(fmt::Error)?;
+            Ok(())
+        }
+    }
+
+    let mut matcher = Matcher(name);
+    write_canonical(&mut matcher, token, text).is_ok() && matcher.0.is_empty()
+}
@@ -2,14 +2,13 @@ use QuoteType as QT;
 use TokenKind as TK;
 
 use crate::{
-    token::{LitType, NumberBase, RawStrErr, Token, TokenFlags},
-    CharInfo, DCharSeq, TokenKind,
+    source_chars::SourceChars,
+    token::{LitType, NumberBase, NumberInfo, RawStrErr, Token, TokenFlags},
+    CharInfo, DCharSeq, DiagCode, DiagSink, Diagnostic, Span, TokenKind,
 };
 
 use clowncc_macros::debug_assert;
-use clowncc_version::StdVersion;
-
-use core::str::Chars;
+use clowncc_version::{LangOptions, StdVersion};
 
 /// Representing a single character delimited string
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -47,6 +46,11 @@ context_type::boolean::yes_no! {
     /// `"` or angle brackets `<` as headers or regular tokens.
     enum ExpectHeader;
 }
+context_type::boolean::yes_no! {
+    /// A universal character starting an identifier must be a valid start
+    /// character rather than only a continuing character.
+    enum IsIdStart;
+}
 context_type::boolean::yes_no! {
     /// Parsing an identifier may perform a few steps before entering the
     /// dedicated function. If a universal char is consumed, it must be
@@ -69,6 +73,18 @@ impl TokenBuilder {
         self.flags |= TokenFlags::UNTERMINATED;
     }
 
+    fn set_digraph(&mut self) {
+        self.flags |= TokenFlags::DIGRAPH;
+    }
+
+    fn set_trigraph(&mut self) {
+        self.flags |= TokenFlags::TRIGRAPH;
+    }
+
+    fn set_ud_suffix(&mut self) {
+        self.flags |= TokenFlags::UD_SUFFIX;
+    }
+
     const fn build(self, kind: TokenKind, end_len_from_end: u32) -> Token {
         let Self {
             start_len_from_end,
@@ -79,23 +95,72 @@ impl TokenBuilder {
 }
 
 pub struct Cursor<'chars> {
-    chars: Chars<'chars>,
-    std_vers: StdVersion,
+    chars: SourceChars<'chars>,
+    lang_opts: LangOptions,
+    source_len: u32,
+    diag_sink: Option<&'chars mut dyn DiagSink>,
     #[cfg(debug_assertions)]
     cur_char: char,
 }
 
 impl<'chars> Cursor<'chars> {
+    /// Creates a cursor over the source for the language options, or for the
+    /// defaults of a [`StdVersion`] or
+    /// [`StdDialect`](clowncc_version::StdDialect).
     #[must_use]
-    pub fn new(code: &'chars str, std_vers: StdVersion) -> Cursor<'chars> {
+    pub fn new(
+        code: &'chars str,
+        lang_opts: impl Into<LangOptions>,
+    ) -> Cursor<'chars> {
+        let lang_opts = lang_opts.into();
+        let mut chars = SourceChars::new(code);
+        chars.set_trigraphs(lang_opts.has_trigraphs());
         Cursor {
-            chars: code.chars(),
-            std_vers,
+            chars,
+            lang_opts,
+            source_len: code
+                .len()
+                .try_into()
+                .expect("Input too large to handle"),
+            diag_sink: None,
             #[cfg(debug_assertions)]
             cur_char: '\0',
         }
     }
 
+    /// Report a [`Diagnostic`] to `sink` for each lexical error and warning.
+    /// The tokens produced are the same with or without a sink.
+    #[must_use]
+    pub fn with_diag_sink(
+        mut self,
+        sink: &'chars mut dyn DiagSink,
+    ) -> Cursor<'chars> {
+        self.diag_sink = Some(sink);
+        self
+    }
+
+    #[must_use]
+    pub(crate) const fn std_vers(&self) -> StdVersion {
+        self.lang_opts.std_version()
+    }
+
+    #[must_use]
+    pub const fn lang_opts(&self) -> LangOptions {
+        self.lang_opts
+    }
+
+    /// The source text which has not been tokenized yet.
+    #[must_use]
+    pub(crate) fn as_str(&self) -> &'chars str {
+        self.chars.as_str()
+    }
+
+    /// Moves the cursor forward by `len` bytes, which must be the start of a
+    /// token for the following tokens to be meaningful.
+    pub(crate) fn skip_bytes(&mut self, len: u32) {
+        self.chars.skip_bytes(len as usize);
+    }
+
     pub fn next_token(&mut self) -> Option<Token> {
         self.next_token_impl(ExpectHeader::No)
     }
@@ -104,35 +169,108 @@ impl<'chars> Cursor<'chars> {
         self.next_token_impl(ExpectHeader::Yes)
     }
 
+    /// The next character as spelled, without removing line splices.
     #[must_use]
-    fn peek_first(&self) -> Option<char> {
+    fn peek_raw(&self) -> Option<char> {
         self.chars.clone().next()
     }
 
+    /// The character `n` places ahead after removing line splices, as
+    /// translation phase 2 does before tokenization.
     #[must_use]
-    fn peek_second(&self) -> Option<char> {
+    fn peek_nth(&self, n: usize) -> Option<char> {
         let mut iter = self.chars.clone();
-        iter.next()?;
+        for _ in 0..n {
+            iter.skip_splices();
+            iter.next()?;
+        }
+        iter.skip_splices();
         iter.next()
     }
 
+    #[must_use]
+    fn peek_first(&self) -> Option<char> {
+        self.peek_nth(0)
+    }
+
+    #[must_use]
+    fn peek_second(&self) -> Option<char> {
+        self.peek_nth(1)
+    }
+
+    #[must_use]
+    fn peek_third(&self) -> Option<char> {
+        self.peek_nth(2)
+    }
+
+    /// Line comments were introduced in C99 and have always been part of C++.
+    #[must_use]
+    fn has_line_comments(&self) -> bool {
+        self.std_vers().is_cpp() || self.std_vers().is_since_c99()
+    }
+
+    /// Digraphs were introduced in C95 and have always been part of C++, but
+    /// can be turned off with `-fno-digraphs`.
+    #[must_use]
+    fn has_digraphs(&self) -> bool {
+        self.lang_opts.has_digraphs()
+    }
+
     fn next_char(&mut self, tb: &mut TokenBuilder) -> Option<char> {
-        let c = self.chars.next()?;
+        let (c, is_trigraph) = self.chars.next_spelled()?;
         if c == '\n' {
             tb.set_newline();
         }
+        if is_trigraph {
+            tb.set_trigraph();
+        }
         #[cfg(debug_assertions)]
         (self.cur_char = c);
         Some(c)
     }
 
+    /// Consumes any line splices as part of the current token, so that the
+    /// lexer agrees with the peek helpers on the next character.
+    fn skip_splices(&mut self, tb: &mut TokenBuilder) {
+        if self.peek_raw() == Some('\\') {
+            self.try_eat_esc_newline(EatSlash::Yes, tb);
+        }
+    }
+
+    /// Consumes the next character after removing line splices, for use
+    /// after matching it through the peek helpers.
+    fn next_char_spliced(&mut self, tb: &mut TokenBuilder) -> Option<char> {
+        self.skip_splices(tb);
+        self.next_char(tb)
+    }
+
+    /// Consumes characters while `predicate` accepts them and returns the
+    /// first rejected character. Runs of ASCII are consumed in bulk and only
+    /// other characters go through [`Self::next_char`].
     fn eat_while(
         &mut self,
         tb: &mut TokenBuilder,
         mut predicate: impl FnMut(char) -> bool,
     ) -> Option<char> {
         loop {
-            let c = self.peek_first()?;
+            let mut has_newline = false;
+            let (_skipped, rejected) = self.chars.skip_ascii_while(|b| {
+                let accepted = predicate(char::from(b));
+                has_newline |= accepted && b == b'\n';
+                accepted
+            });
+            if has_newline {
+                tb.set_newline();
+            }
+            #[cfg(debug_assertions)]
+            if let Some(&last) = _skipped.as_bytes().last() {
+                self.cur_char = char::from(last);
+            }
+            if rejected.is_some() {
+                return rejected;
+            }
+
+            let c = self.peek_raw()?;
             if !predicate(c) {
                 return Some(c);
             }
@@ -149,6 +287,40 @@ impl<'chars> Cursor<'chars> {
             .expect("Input too large to handle")
     }
 
+    /// Reports a diagnostic spanning between two positions given as lengths
+    /// from the end, like the positions kept by [`TokenBuilder`].
+    fn report(
+        &mut self,
+        code: DiagCode,
+        start_len_from_end: u32,
+        end_len_from_end: u32,
+    ) {
+        if let Some(sink) = self.diag_sink.as_deref_mut() {
+            let span = Span::new(
+                self.source_len - start_len_from_end,
+                self.source_len - end_len_from_end,
+            );
+            sink.report(Diagnostic::new(code, span));
+        }
+    }
+
+    /// Reports the error encoded in a finished token, if any.
+    fn diagnose_token(&mut self, token: &Token, start_len_from_end: u32) {
+        let code = match token.kind() {
+            TK::Unknown => DiagCode::UnknownChar,
+            TK::StrayBackSlash => DiagCode::StrayBackSlash,
+            TK::StrayNumPrefix { .. } => DiagCode::StrayNumPrefix,
+            TK::BadRawStr(err) => err.into(),
+            _ if !token.flags().is_unterminated() => return,
+            TK::Str { .. } => DiagCode::UnterminatedStr,
+            TK::CharSeq { .. } => DiagCode::UnterminatedCharSeq,
+            TK::Header | TK::SystemHeader => DiagCode::UnterminatedHeader,
+            // Block comments point at their opener and report it themselves
+            _ => return,
+        };
+        self.report(code, start_len_from_end, self.len_from_end());
+    }
+
     #[must_use]
     fn make_token_builder(&self) -> TokenBuilder {
         TokenBuilder {
@@ -161,31 +333,43 @@ impl<'chars> Cursor<'chars> {
         let mut token_builder = self.make_token_builder();
         let tb = &mut token_builder;
 
-        let kind = match self.next_char(tb)? {
+        // Line splices are removed before tokenization, so any before the
+        // token are part of it. Only at the end of input is there no token.
+        self.skip_splices(tb);
+        let Some(first_char) = self.next_char(tb) else {
+            let splits_lines = false;
+            return (self.len_from_end() != token_builder.start_len_from_end)
+                .then(|| {
+                    token_builder.build(
+                        TK::Whitespace { splits_lines },
+                        self.len_from_end(),
+                    )
+                });
+        };
+
+        let kind = match first_char {
             c if c.is_whitespace() => self.eat_whitespace(c == '\n', tb),
 
             '/' => match self.peek_first() {
-                Some('/') => self.eat_line_comment(tb),
+                Some('/') if self.has_line_comments() => {
+                    self.eat_line_comment(tb)
+                }
                 Some('*') => self.eat_block_comment(tb),
+                Some('=') => self.eat_punctuator(TK::SlashEqual, tb),
                 Some(_) | None => TK::Slash,
             },
 
             'L' => self.eat_lit_or_identifier(LitType::Wide, tb),
-            'U' if self.std_vers.is_since_c11()
-                || self.std_vers.is_since_cpp11() =>
-            {
-                self.eat_lit_or_identifier(LitType::Utf32, tb)
-            }
-            'u' if self.std_vers.is_since_c11()
-                || self.std_vers.is_since_cpp11() =>
-            {
-                self.eat_lit_or_identifier_u(tb)
-            }
-            'R' if self.std_vers.is_since_cpp11() => {
+            'U' => self.eat_lit_or_identifier(LitType::Utf32, tb),
+            'u' => self.eat_lit_or_identifier_u(tb),
+            'R' if self.std_vers().is_since_cpp11() => {
                 self.eat_raw_str_or_identifier(LitType::Default, tb)
             }
 
             c if c.is_id_start() => self.eat_identifier(HasUnivChar::No, tb),
+            '$' if self.lang_opts.has_dollars_in_identifiers() => {
+                self.eat_identifier(HasUnivChar::No, tb)
+            }
             c @ '0'..='9' => self.eat_numbers(c, tb),
 
             '"' if header.is_yes() => {
@@ -199,26 +383,67 @@ impl<'chars> Cursor<'chars> {
             '\'' => self.eat_quoted_list(QT::CharSeq, LitType::Default, tb),
 
             ';' => TK::SemiColon,
-            '#' => TK::Pound,
-            '&' => TK::Ampersand,
-            '|' => TK::Pipe,
-            '.' => TK::Dot,
+            '#' => match self.peek_first() {
+                Some('#') => self.eat_punctuator(TK::PoundPound, tb),
+                Some(_) | None => TK::Pound,
+            },
+            '&' => match self.peek_first() {
+                Some('&') => self.eat_punctuator(TK::AmpersandAmpersand, tb),
+                Some('=') => self.eat_punctuator(TK::AmpersandEqual, tb),
+                Some(_) | None => TK::Ampersand,
+            },
+            '|' => match self.peek_first() {
+                Some('|') => self.eat_punctuator(TK::PipePipe, tb),
+                Some('=') => self.eat_punctuator(TK::PipeEqual, tb),
+                Some(_) | None => TK::Pipe,
+            },
+            '.' => self.eat_dot(tb),
             ',' => TK::Comma,
 
             '?' => TK::QuestionMark,
-            ':' => TK::Colon,
-
-            '=' => TK::Equal,
-            '+' => TK::Plus,
-            '-' => TK::Minus,
-            '*' => TK::Star,
-            '%' => TK::Percent,
-            '!' => TK::Exclamation,
+            ':' => match self.peek_first() {
+                Some(':')
+                    if self.std_vers().is_cpp()
+                        || self.std_vers().is_since_c23() =>
+                {
+                    self.eat_punctuator(TK::ColonColon, tb)
+                }
+                Some('>') if self.has_digraphs() => {
+                    self.eat_digraph(TK::CloseBracket, tb)
+                }
+                Some(_) | None => TK::Colon,
+            },
+
+            '=' => match self.peek_first() {
+                Some('=') => self.eat_punctuator(TK::EqualEqual, tb),
+                Some(_) | None => TK::Equal,
+            },
+            '+' => match self.peek_first() {
+                Some('+') => self.eat_punctuator(TK::PlusPlus, tb),
+                Some('=') => self.eat_punctuator(TK::PlusEqual, tb),
+                Some(_) | None => TK::Plus,
+            },
+            '-' => self.eat_minus(tb),
+            '*' => match self.peek_first() {
+                Some('=') => self.eat_punctuator(TK::StarEqual, tb),
+                Some(_) | None => TK::Star,
+            },
+            '%' => self.eat_percent(tb),
+            '!' => match self.peek_first() {
+                Some('=') => self.eat_punctuator(TK::ExclamationEqual, tb),
+                Some(_) | None => TK::Exclamation,
+            },
             '~' => TK::Tilde,
-            '^' => TK::Caret,
+            '^' => match self.peek_first() {
+                Some('=') => self.eat_punctuator(TK::CaretEqual, tb),
+                Some('^') if self.std_vers().is_since_cpp26() => {
+                    self.eat_punctuator(TK::CaretCaret, tb)
+                }
+                Some(_) | None => TK::Caret,
+            },
 
-            '<' => TK::GreaterThan,
-            '>' => TK::LessThan,
+            '<' => self.eat_less_than(tb),
+            '>' => self.eat_greater_than(tb),
 
             '(' => TK::OpenParen,
             ')' => TK::CloseParen,
@@ -228,52 +453,208 @@ impl<'chars> Cursor<'chars> {
             ']' => TK::CloseBracket,
 
             '\\' => match self.peek_first() {
-                Some('u' | 'U' | '\\')
-                    if self.try_eat_universal_char(EatSlash::No, tb) =>
+                Some('u' | 'U')
+                    if self.try_eat_universal_char(
+                        EatSlash::No,
+                        IsIdStart::Yes,
+                        tb,
+                    ) =>
                 {
                     self.eat_identifier(HasUnivChar::Yes, tb)
                 }
-                Some(c) if c.is_whitespace() => {
-                    debug_assert!(!self.try_eat_esc_newline(EatSlash::No, tb));
-                    TK::StrayBackSlash
-                }
                 Some(_) | None => TK::StrayBackSlash,
             },
             _ => TK::Unknown,
         };
 
-        Some(token_builder.build(kind, self.len_from_end()))
+        let start_len_from_end = token_builder.start_len_from_end;
+        let token = token_builder.build(kind, self.len_from_end());
+        if self.diag_sink.is_some() {
+            self.diagnose_token(&token, start_len_from_end);
+        }
+        Some(token)
+    }
+
+    /// Consumes the final character of a multi-char punctuator that has
+    /// already been matched through peeking.
+    fn eat_punctuator(
+        &mut self,
+        kind: TokenKind,
+        tb: &mut TokenBuilder,
+    ) -> TokenKind {
+        self.next_char_spliced(tb);
+        kind
+    }
+
+    /// Consumes the final character of a digraph that has already been matched
+    /// through peeking.
+    fn eat_digraph(
+        &mut self,
+        kind: TokenKind,
+        tb: &mut TokenBuilder,
+    ) -> TokenKind {
+        tb.set_digraph();
+        self.eat_punctuator(kind, tb)
+    }
+
+    fn eat_percent(&mut self, tb: &mut TokenBuilder) -> TokenKind {
+        debug_assert!(self.cur_char == '%');
+        match self.peek_first() {
+            Some('=') => self.eat_punctuator(TK::PercentEqual, tb),
+            Some('>') if self.has_digraphs() => {
+                self.eat_digraph(TK::CloseBrace, tb)
+            }
+            Some(':') if self.has_digraphs() => {
+                if (self.peek_second(), self.peek_third())
+                    == (Some('%'), Some(':'))
+                {
+                    self.next_char_spliced(tb);
+                    self.next_char_spliced(tb);
+                    self.eat_digraph(TK::PoundPound, tb)
+                } else {
+                    self.eat_digraph(TK::Pound, tb)
+                }
+            }
+            Some(_) | None => TK::Percent,
+        }
+    }
+
+    fn eat_dot(&mut self, tb: &mut TokenBuilder) -> TokenKind {
+        debug_assert!(self.cur_char == '.');
+        match self.peek_first() {
+            Some('.') if self.peek_second() == Some('.') => {
+                self.next_char_spliced(tb);
+                self.eat_punctuator(TK::Ellipsis, tb)
+            }
+            Some('*') if self.std_vers().is_cpp() => {
+                self.eat_punctuator(TK::DotStar, tb)
+            }
+            Some(c) if c.is_ascii_digit() => self.eat_numbers('.', tb),
+            Some(_) | None => TK::Dot,
+        }
+    }
+
+    fn eat_minus(&mut self, tb: &mut TokenBuilder) -> TokenKind {
+        debug_assert!(self.cur_char == '-');
+        match self.peek_first() {
+            Some('-') => self.eat_punctuator(TK::MinusMinus, tb),
+            Some('=') => self.eat_punctuator(TK::MinusEqual, tb),
+            Some('>') => {
+                self.next_char_spliced(tb);
+                match self.peek_first() {
+                    Some('*') if self.std_vers().is_cpp() => {
+                        self.eat_punctuator(TK::ArrowStar, tb)
+                    }
+                    Some(_) | None => TK::Arrow,
+                }
+            }
+            Some(_) | None => TK::Minus,
+        }
+    }
+
+    fn eat_less_than(&mut self, tb: &mut TokenBuilder) -> TokenKind {
+        debug_assert!(self.cur_char == '<');
+        match self.peek_first() {
+            Some('<') => {
+                self.next_char_spliced(tb);
+                match self.peek_first() {
+                    Some('=') => {
+                        self.eat_punctuator(TK::LessThanLessThanEqual, tb)
+                    }
+                    Some(_) | None => TK::LessThanLessThan,
+                }
+            }
+            Some('=') => {
+                self.next_char_spliced(tb);
+                match self.peek_first() {
+                    Some('>') if self.std_vers().is_since_cpp20() => {
+                        self.eat_punctuator(TK::Spaceship, tb)
+                    }
+                    Some(_) | None => TK::LessThanEqual,
+                }
+            }
+            // C++11 [lex.pptoken]: `<::` not followed by `:` or `>` lexes the
+            // `<` on its own so that `std::vector<::T>` works.
+            Some(':')
+                if self.std_vers().is_since_cpp11()
+                    && self.peek_second() == Some(':')
+                    && !matches!(self.peek_third(), Some(':' | '>')) =>
+            {
+                TK::LessThan
+            }
+            Some(':') if self.has_digraphs() => {
+                self.eat_digraph(TK::OpenBracket, tb)
+            }
+            Some('%') if self.has_digraphs() => {
+                self.eat_digraph(TK::OpenBrace, tb)
+            }
+            Some(_) | None => TK::LessThan,
+        }
+    }
+
+    fn eat_greater_than(&mut self, tb: &mut TokenBuilder) -> TokenKind {
+        debug_assert!(self.cur_char == '>');
+        match self.peek_first() {
+            Some('>') => {
+                self.next_char_spliced(tb);
+                match self.peek_first() {
+                    Some('=') => {
+                        self.eat_punctuator(TK::GreaterThanGreaterThanEqual, tb)
+                    }
+                    Some(_) | None => TK::GreaterThanGreaterThan,
+                }
+            }
+            Some('=') => self.eat_punctuator(TK::GreaterThanEqual, tb),
+            Some(_) | None => TK::GreaterThan,
+        }
     }
 
     fn eat_line_comment(&mut self, tb: &mut TokenBuilder) -> TokenKind {
         debug_assert!(self.cur_char == '/' && self.peek_first() == Some('/'));
+        self.next_char_spliced(tb); // Consume second slash as part of opener
 
         while let Some(c) = self.eat_while(tb, |c| !matches!(c, '\\' | '\n')) {
             match c {
                 '\n' => break,
                 '\\' => {
+                    let slash_start = self.len_from_end();
                     self.next_char(tb);
-                    self.try_eat_esc_newline(EatSlash::No, tb);
+                    if self.try_eat_esc_newline(EatSlash::No, tb) {
+                        let code = DiagCode::MultiLineLineComment;
+                        self.report(code, slash_start, self.len_from_end());
+                    }
                 }
                 _ => unreachable!(),
             }
         }
 
-        debug_assert!(matches!(self.peek_first(), Some('\n') | None));
+        debug_assert!(matches!(self.peek_raw(), Some('\n') | None));
         TK::LineComment
     }
 
     fn eat_block_comment(&mut self, tb: &mut TokenBuilder) -> TokenKind {
         debug_assert!(self.cur_char == '/' && self.peek_first() == Some('*'));
-        self.next_char(tb); // Consume first star as part of opener
-        while let Some(c) = self.next_char(tb) {
-            if c != '*' || self.peek_first() != Some('/') {
-                continue;
+        self.next_char_spliced(tb); // Consume first star as part of opener
+        let opener_end = self.len_from_end();
+        loop {
+            self.eat_while(tb, |c| !matches!(c, '*' | '/'));
+            let char_start = self.len_from_end();
+            match (self.next_char(tb), self.peek_first()) {
+                (Some('*'), Some('/')) => {
+                    self.next_char_spliced(tb);
+                    return TK::BlockComment;
+                }
+                (Some('/'), Some('*')) => {
+                    let code = DiagCode::NestedBlockComment;
+                    self.report(code, char_start, self.len_from_end() - 1);
+                }
+                (Some(_), _) => {}
+                (None, _) => break,
             }
-            self.next_char(tb);
-            return TK::BlockComment;
         }
         tb.set_unterminated();
+        let code = DiagCode::UnterminatedBlockComment;
+        self.report(code, tb.start_len_from_end, opener_end);
         TK::BlockComment
     }
 
@@ -300,8 +681,14 @@ impl<'chars> Cursor<'chars> {
         tb: &mut TokenBuilder,
     ) -> TokenKind {
         let mut has_univ_char = has_univ_char.is_yes();
-        while let Some('\\') = self.eat_while(tb, char::is_id_continue) {
-            if !self.try_eat_universal_char(EatSlash::Yes, tb) {
+        let dollars = self.lang_opts.has_dollars_in_identifiers();
+        while let Some('\\') =
+            self.eat_while(tb, |c| c.is_id_continue() || (dollars && c == '$'))
+        {
+            if self.try_eat_esc_newline(EatSlash::Yes, tb) {
+                continue;
+            }
+            if !self.try_eat_universal_char(EatSlash::Yes, IsIdStart::No, tb) {
                 break;
             }
             has_univ_char = true;
@@ -309,40 +696,215 @@ impl<'chars> Cursor<'chars> {
         TK::Identifier { has_univ_char }
     }
 
+    /// Digit separators `'` were introduced in C++14 and C23.
+    #[must_use]
+    fn has_digit_sep(&self) -> bool {
+        self.std_vers().is_since_c23() || self.std_vers().is_since_cpp14()
+    }
+
+    /// Hexadecimal floats, and the `p sign` pp-number rule, were introduced in
+    /// C99 and C++17.
+    #[must_use]
+    fn has_hex_float(&self) -> bool {
+        self.std_vers().is_since_c99() || self.std_vers().is_since_cpp17()
+    }
+
+    /// Lexes a full preprocessing number, `first_char` being either a digit or
+    /// a period followed by a digit. The numeric body is lexed according to
+    /// the literal grammar and the remainder of the pp-number is the suffix.
     fn eat_numbers(
         &mut self,
         first_char: char,
         tb: &mut TokenBuilder,
     ) -> TokenKind {
         debug_assert!(
-            self.cur_char.is_ascii_digit() && self.cur_char == first_char
+            self.cur_char == first_char
+                && (first_char.is_ascii_digit()
+                    || (first_char == '.'
+                        && self
+                            .peek_first()
+                            .is_some_and(|c| c.is_ascii_digit())))
         );
-        let base = if first_char == '0' {
-            self.eat_number_base(tb)
-        } else {
-            NumberBase::Decimal
+        let base = match first_char {
+            '0' => self.eat_number_base(tb),
+            _ => NumberBase::Decimal,
         };
+        let mut info = NumberInfo::new(base);
 
-        let match_num = |c| base.matches(c);
+        let last_char = match base {
+            NumberBase::Decimal | NumberBase::Octal => {
+                self.eat_decimal_body(first_char, &mut info, tb);
+                Some(first_char)
+            }
+            NumberBase::Hexidecimal => self.eat_hex_body(&mut info, tb),
+            NumberBase::Binary => {
+                self.eat_digits(NumberBase::Binary, false, &mut info, tb)
+            }
+        };
 
-        if base != NumberBase::Decimal
-            && !self.peek_first().map_or(false, match_num)
+        let suffix_start = self.len_from_end();
+        // The `e sign` rule applies to hexadecimal digits too, i.e. `0x1e+1`
+        if matches!(last_char, Some('e' | 'E'))
+            && matches!(self.peek_first(), Some('+' | '-'))
         {
-            return TK::StrayNumPrefix { base };
+            self.next_char_spliced(tb);
         }
+        self.eat_pp_number_suffix(tb);
+        info.set_suffix_len(suffix_start - self.len_from_end());
 
-        let mut has_sep = false;
-        while let Some('\'') = self.eat_while(tb, match_num) {
-            if !(self.std_vers.is_since_c23() || self.std_vers.is_since_cpp14())
-                || !self.peek_second().map_or(false, match_num)
-            {
-                break;
+        if last_char.is_some() {
+            TK::Number(info)
+        } else {
+            TK::StrayNumPrefix { base }
+        }
+    }
+
+    fn eat_decimal_body(
+        &mut self,
+        first_char: char,
+        info: &mut NumberInfo,
+        tb: &mut TokenBuilder,
+    ) {
+        let dec = NumberBase::Decimal;
+        let mut has_period = first_char == '.';
+        let has_more_digits =
+            self.eat_digits(dec, !has_period, info, tb).is_some();
+        if !has_period && self.peek_first() == Some('.') {
+            self.next_char_spliced(tb);
+            self.eat_digits(dec, false, info, tb);
+            has_period = true;
+        }
+        if has_period {
+            info.set_float();
+        }
+        if self.try_eat_exponent(['e', 'E'], info, tb) {
+            info.set_exponent();
+        }
+        // Leading zero makes an integer octal, invalid digits are left for
+        // later stages to diagnose.
+        if first_char == '0' && has_more_digits && !info.is_float() {
+            info.set_base(NumberBase::Octal);
+        }
+    }
+
+    /// Returns the last character of the body, or [`None`] if the body has no
+    /// digits.
+    fn eat_hex_body(
+        &mut self,
+        info: &mut NumberInfo,
+        tb: &mut TokenBuilder,
+    ) -> Option<char> {
+        let hex = NumberBase::Hexidecimal;
+        let mut last_char = self.eat_digits(hex, false, info, tb);
+        if self.peek_first() == Some('.')
+            && (last_char.is_some()
+                || self.peek_second().is_some_and(|c| hex.matches(c)))
+        {
+            self.next_char_spliced(tb);
+            last_char = self.eat_digits(hex, false, info, tb).or(Some('.'));
+            info.set_float();
+        }
+        if last_char.is_some()
+            && self.has_hex_float()
+            && self.try_eat_exponent(['p', 'P'], info, tb)
+        {
+            info.set_exponent();
+            last_char = Some('0');
+        }
+        last_char
+    }
+
+    /// Eats a sequence of digits of the base along with separators which are
+    /// between digits. Returns the last digit consumed, if any.
+    fn eat_digits(
+        &mut self,
+        base: NumberBase,
+        after_digit: bool,
+        info: &mut NumberInfo,
+        tb: &mut TokenBuilder,
+    ) -> Option<char> {
+        let match_num = |c| base.matches(c);
+        let mut last_digit = None;
+        loop {
+            match self.peek_first() {
+                Some(c) if match_num(c) => last_digit = Some(c),
+                Some('\'')
+                    if (last_digit.is_some() || after_digit)
+                        && self.has_digit_sep()
+                        && self.peek_second().is_some_and(match_num) =>
+                {
+                    info.set_sep();
+                }
+                Some(_) | None => return last_digit,
             }
-            has_sep = true;
-            self.next_char(tb); // eat '
+            self.next_char_spliced(tb);
+        }
+    }
+
+    /// Eats an exponent if there is at least one digit following the marker
+    /// and optional sign.
+    fn try_eat_exponent(
+        &mut self,
+        markers: [char; 2],
+        info: &mut NumberInfo,
+        tb: &mut TokenBuilder,
+    ) -> bool {
+        let is_digit = |c: Option<char>| c.is_some_and(|c| c.is_ascii_digit());
+        if !self.peek_first().is_some_and(|c| markers.contains(&c)) {
+            return false;
+        }
+        let has_sign = matches!(self.peek_second(), Some('+' | '-'));
+        if !is_digit(if has_sign {
+            self.peek_third()
+        } else {
+            self.peek_second()
+        }) {
+            return false;
         }
+        self.next_char_spliced(tb);
+        if has_sign {
+            self.next_char_spliced(tb);
+        }
+        self.eat_digits(NumberBase::Decimal, false, info, tb)
+            .is_some()
+    }
 
-        TK::Number { base, has_sep }
+    /// Eats the remainder of the pp-number grammar which has not been covered
+    /// by the numeric body.
+    fn eat_pp_number_suffix(&mut self, tb: &mut TokenBuilder) {
+        loop {
+            match self.peek_first() {
+                Some('e' | 'E')
+                    if matches!(self.peek_second(), Some('+' | '-')) =>
+                {
+                    self.next_char_spliced(tb);
+                }
+                Some('p' | 'P')
+                    if self.has_hex_float()
+                        && matches!(self.peek_second(), Some('+' | '-')) =>
+                {
+                    self.next_char_spliced(tb);
+                }
+                Some('\'')
+                    if self.has_digit_sep()
+                        && self
+                            .peek_second()
+                            .is_some_and(char::is_id_continue) => {}
+                Some('\\') => {
+                    if !self.try_eat_universal_char(
+                        EatSlash::Yes,
+                        IsIdStart::No,
+                        tb,
+                    ) {
+                        return;
+                    }
+                    continue;
+                }
+                Some(c) if c == '.' || c.is_id_continue() => {}
+                Some(_) | None => return,
+            }
+            self.next_char_spliced(tb);
+        }
     }
 
     fn eat_number_base(&mut self, tb: &mut TokenBuilder) -> NumberBase {
@@ -350,13 +912,11 @@ impl<'chars> Cursor<'chars> {
         let base = match self.peek_first() {
             Some('b' | 'B') => NumberBase::Binary,
             Some('x' | 'X') => NumberBase::Hexidecimal,
-            // Return here to not eat the non-prefix character that follows
-            Some(c) if NumberBase::Octal.matches(c) => {
-                return NumberBase::Octal
-            }
+            // Octal is decided after the body is lexed since `0` may lead a
+            // decimal float, i.e. `09.5`
             Some(_) | None => return NumberBase::Decimal,
         };
-        self.next_char(tb);
+        self.next_char_spliced(tb);
         base
     }
 
@@ -373,31 +933,50 @@ impl<'chars> Cursor<'chars> {
                 | ('U', LitType::Utf32)
         ));
         match self.peek_first() {
-            None => TK::Identifier {
-                has_univ_char: false,
-            },
-            // TODO: gaurd based on stdversion
-            Some('\'') => self.eat_quoted_list(QT::CharSeq, prefix, tb),
-            Some('"') => self.eat_quoted_list(QT::String, prefix, tb),
-            Some('R') => {
-                self.next_char(tb);
+            Some('\'') if self.has_lit_prefix(QT::CharSeq, prefix) => {
+                self.next_char_spliced(tb);
+                self.eat_quoted_list(QT::CharSeq, prefix, tb)
+            }
+            Some('"') if self.has_lit_prefix(QT::String, prefix) => {
+                self.next_char_spliced(tb);
+                self.eat_quoted_list(QT::String, prefix, tb)
+            }
+            Some('R')
+                if self.std_vers().is_since_cpp11()
+                    && self.has_lit_prefix(QT::String, prefix) =>
+            {
+                self.next_char_spliced(tb);
                 self.eat_raw_str_or_identifier(prefix, tb)
             }
-            Some(_) => self.eat_identifier(HasUnivChar::No, tb),
+            _ => self.eat_identifier(HasUnivChar::No, tb),
+        }
+    }
+
+    /// Whether `prefix` introduces a literal of `quote_ty` in this version.
+    /// `L` has always been a prefix while `u` and `U` came with C11 and C++11.
+    /// `u8` arrived with them for strings but only in C23 and C++17 for
+    /// character literals. Otherwise the prefix lexes as an identifier.
+    fn has_lit_prefix(&self, quote_ty: QuoteType, prefix: LitType) -> bool {
+        let sv = self.std_vers();
+        match (quote_ty, prefix) {
+            (_, LitType::Default | LitType::Wide) => true,
+            (QT::CharSeq, LitType::Utf8) => {
+                sv.is_since_c23() || sv.is_since_cpp17()
+            }
+            (_, LitType::Utf8 | LitType::Utf16 | LitType::Utf32) => {
+                sv.is_since_c11() || sv.is_since_cpp11()
+            }
         }
     }
 
     fn eat_lit_or_identifier_u(&mut self, tb: &mut TokenBuilder) -> TokenKind {
         debug_assert!(self.cur_char == 'u');
         match self.peek_first() {
-            None => TK::Identifier {
-                has_univ_char: false,
-            },
             Some('8') => {
-                self.next_char(tb);
+                self.next_char_spliced(tb);
                 self.eat_lit_or_identifier(LitType::Utf8, tb)
             }
-            Some(_) => self.eat_lit_or_identifier(LitType::Utf16, tb),
+            _ => self.eat_lit_or_identifier(LitType::Utf16, tb),
         }
     }
 
@@ -428,8 +1007,13 @@ impl<'chars> Cursor<'chars> {
                 }
                 Some('\\') => {
                     has_esc = true;
-                    match self.peek_first() {
-                        None | Some('\\') => {}
+                    // Line splices may separate the escaped character
+                    while self.peek_raw() == Some('\\')
+                        && self.try_eat_esc_newline(EatSlash::Yes, tb)
+                    {
+                    }
+                    match self.peek_raw() {
+                        None => {}
                         Some(c) if c.is_whitespace() => {
                             self.try_eat_esc_newline(EatSlash::No, tb);
                         }
@@ -443,6 +1027,8 @@ impl<'chars> Cursor<'chars> {
         };
         if unterminated {
             tb.set_unterminated();
+        } else if matches!(quote_ty, QT::String | QT::CharSeq) {
+            self.try_eat_ud_suffix(tb);
         }
         match quote_ty {
             QT::String => TK::Str { lit_type, has_esc },
@@ -452,6 +1038,28 @@ impl<'chars> Cursor<'chars> {
         }
     }
 
+    /// Since C++11 an identifier directly following a string or character
+    /// literal is a ud-suffix and part of the literal token, i.e. `"abc"_sv`.
+    fn try_eat_ud_suffix(&mut self, tb: &mut TokenBuilder) {
+        if !self.std_vers().is_since_cpp11() {
+            return;
+        }
+        match self.peek_first() {
+            Some(c) if c.is_id_start() => {
+                self.next_char_spliced(tb);
+            }
+            Some('\\')
+                if self.try_eat_universal_char(
+                    EatSlash::Yes,
+                    IsIdStart::Yes,
+                    tb,
+                ) => {}
+            Some(_) | None => return,
+        }
+        self.eat_identifier(HasUnivChar::No, tb);
+        tb.set_ud_suffix();
+    }
+
     fn eat_raw_str_or_identifier(
         &mut self,
         prefix: LitType,
@@ -459,11 +1067,8 @@ impl<'chars> Cursor<'chars> {
     ) -> TokenKind {
         debug_assert!(self.cur_char == 'R');
         match self.peek_first() {
-            None => TK::Identifier {
-                has_univ_char: false,
-            },
             Some('"') => self.eat_raw_string(prefix, tb),
-            Some(_) => self.eat_identifier(HasUnivChar::No, tb),
+            _ => self.eat_identifier(HasUnivChar::No, tb),
         }
     }
 
@@ -473,15 +1078,21 @@ impl<'chars> Cursor<'chars> {
         tb: &mut TokenBuilder,
     ) -> TokenKind {
         debug_assert!(self.cur_char == 'R' && self.peek_first() == Some('"'));
-        self.next_char(tb); // Consume the starting quote
+        self.next_char_spliced(tb); // Consume the starting quote
 
-        let d_char_seq = match self.eat_raw_d_char_prefix(tb) {
-            Ok(dcs) => dcs,
-            Err(invalid) => return invalid,
+        // Trigraph replacement is reverted within raw strings, so lex the body
+        // on the original spelling.
+        let trigraphs = self.chars.trigraphs();
+        self.chars.set_trigraphs(false);
+        let kind = match self.eat_raw_d_char_prefix(tb) {
+            Ok(d_char_seq) => {
+                debug_assert!(self.cur_char == '(');
+                self.eat_raw_str_after_prefix(d_char_seq, prefix, tb)
+            }
+            Err(invalid) => invalid,
         };
-        debug_assert!(self.cur_char == '(');
-
-        self.eat_raw_str_after_prefix(d_char_seq, prefix, tb)
+        self.chars.set_trigraphs(trigraphs);
+        kind
     }
 
     fn eat_raw_d_char_prefix(
@@ -492,7 +1103,7 @@ impl<'chars> Cursor<'chars> {
         let prefix_start = self.len_from_end();
         let prefix_char = match self.next_char(tb) {
             Some('(') => return Ok(DCharSeq::empty()),
-            Some(c) if c.is_d_char(self.std_vers) => c,
+            Some(c) if c.is_d_char(self.std_vers()) => c,
             Some(_) => return Err(TK::BadRawStr(RawStrErr::NotDChar)),
             None => return Err(TK::BadRawStr(RawStrErr::UnterminatedInPrefix)),
         };
@@ -523,10 +1134,12 @@ impl<'chars> Cursor<'chars> {
         tb: &mut TokenBuilder,
     ) -> TokenKind {
         loop {
-            match self.eat_while(tb, |c| c != ')') {
+            match self.eat_while(tb, |c| c != ')' && c.is_r_char()) {
                 None => return TK::BadRawStr(RawStrErr::Unterminated),
-                Some(')') => {}
-                Some(_) => unreachable!(),
+                Some(')') => {
+                    self.next_char(tb);
+                }
+                Some(_) => unreachable!("every char is an r-char"),
             }
             if let Some(result) = self.eat_raw_str_suffix(delim, lit_type, tb) {
                 return result;
@@ -546,6 +1159,7 @@ impl<'chars> Cursor<'chars> {
             Some('"') if start - self.len_from_end() != expected => None,
             Some('"') => {
                 self.next_char(tb);
+                self.try_eat_ud_suffix(tb);
                 Some(TK::RawStr { lit_type, delim })
             }
             Some(_) => None,
@@ -560,14 +1174,16 @@ impl<'chars> Cursor<'chars> {
     ) -> bool {
         let mut chars_dup = self.chars.clone();
         let mut tb_dup = tb.clone();
-        if eat_slash.is_yes() && self.peek_first() == Some('\\') {
+        let mut has_splice = false;
+        if eat_slash.is_yes() && self.peek_raw() == Some('\\') {
             self.next_char(tb);
         }
         while let Some('\n') =
             self.eat_while(tb, |c| c.is_whitespace() && c != '\n')
         {
             self.next_char(tb); // eat new line
-            if self.peek_first() == Some('\\') {
+            has_splice = true;
+            if self.peek_raw() == Some('\\') {
                 chars_dup = self.chars.clone();
                 tb_dup = tb.clone();
                 self.next_char(tb); // eat slash
@@ -575,9 +1191,43 @@ impl<'chars> Cursor<'chars> {
                 return true;
             }
         }
+        // Back up to after the last splice, if there was one
         *tb = tb_dup;
         self.chars = chars_dup;
-        false
+        #[cfg(debug_assertions)]
+        if has_splice {
+            self.cur_char = '\n';
+        }
+        has_splice
+    }
+
+    /// Universal characters in identifiers must name an identifier character
+    /// of the translation set which could not have been spelled directly.
+    fn diagnose_ucn(
+        &mut self,
+        value: u32,
+        is_id_start: IsIdStart,
+        start_len_from_end: u32,
+    ) {
+        if self.diag_sink.is_none() {
+            return;
+        }
+        let is_id_char = if is_id_start.is_yes() {
+            value.is_id_start()
+        } else {
+            value.is_id_continue()
+        };
+        let is_c_basic = self.std_vers().is_c()
+            && value < 0xA0
+            && !matches!(value, 0x24 | 0x40 | 0x60);
+        let code = if !value.is_in_translation_set() || is_c_basic {
+            DiagCode::InvalidUcn
+        } else if !is_id_char || value.is_in_basic_set(self.std_vers()) {
+            DiagCode::InvalidUcnInIdentifier
+        } else {
+            return;
+        };
+        self.report(code, start_len_from_end, self.len_from_end());
     }
 
     #[cold]
@@ -585,30 +1235,32 @@ impl<'chars> Cursor<'chars> {
     fn try_eat_universal_char(
         &mut self,
         eat_slash: EatSlash,
+        is_id_start: IsIdStart,
         tb: &mut TokenBuilder,
     ) -> bool {
-        fn internal(this: &mut Cursor<'_>, tb: &mut TokenBuilder) -> bool {
-            let start_char = match this.peek_first() {
-                Some('\\') if this.try_eat_esc_newline(EatSlash::Yes, tb) => {
-                    this.peek_first()
-                }
-                c => c,
-            };
-            let max_count = match start_char {
+        /// Returns the value of the universal character if it is complete.
+        fn internal(
+            this: &mut Cursor<'_>,
+            tb: &mut TokenBuilder,
+        ) -> Option<u32> {
+            this.skip_splices(tb);
+            let max_count = match this.peek_raw() {
                 Some('u') => 4,
                 Some('U') => 8,
                 Some(_) | None => {
-                    return false;
+                    return None;
                 }
             };
             this.next_char(tb);
             let mut count = 0;
+            let mut value = 0;
             while let Some(c) = this.next_char(tb) {
                 match c {
                     c if NumberBase::Hexidecimal.matches(c) => {
+                        value = value << 4 | c.to_digit(16)?;
                         count += 1;
                         if count == max_count {
-                            return true;
+                            return Some(value);
                         }
                     }
                     '\\' if this.try_eat_esc_newline(EatSlash::No, tb) => {
@@ -617,20 +1269,31 @@ impl<'chars> Cursor<'chars> {
                     _ => break,
                 }
             }
-            false
+            None
         }
 
         let chars_dup = self.chars.clone();
         let tb_dup = tb.clone();
+        // Without eating it here, the slash is the start of the token.
+        let start = if eat_slash.is_yes() {
+            self.len_from_end()
+        } else {
+            tb.start_len_from_end
+        };
         if eat_slash.is_yes() && self.peek_first() == Some('\\') {
-            self.next_char(tb);
+            self.next_char_spliced(tb);
         }
 
-        let result = internal(self, tb);
-        if !result {
-            *tb = tb_dup;
-            self.chars = chars_dup;
+        match internal(self, tb) {
+            Some(value) => {
+                self.diagnose_ucn(value, is_id_start, start);
+                true
+            }
+            None => {
+                *tb = tb_dup;
+                self.chars = chars_dup;
+                false
+            }
         }
-        result
     }
 }
@@ -0,0 +1,240 @@
+use core::fmt;
+
+use crate::RawStrErr;
+
+/// How serious a [`Diagnostic`] is. Errors make the source ill-formed while
+/// warnings concern valid but suspicious source.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// Unique code for each problem the lexer can report.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u16)]
+pub enum DiagCode {
+    UnknownChar = 1,
+    StrayBackSlash = 2,
+    StrayNumPrefix = 3,
+    UnterminatedBlockComment = 4,
+    UnterminatedStr = 5,
+    UnterminatedCharSeq = 6,
+    UnterminatedHeader = 7,
+    RawStrNotDChar = 8,
+    RawStrPrefixMultiChar = 9,
+    RawStrPrefixTooLong = 10,
+    RawStrUnterminated = 11,
+    RawStrUnterminatedInPrefix = 12,
+    RawStrUnterminatedInSuffix = 13,
+    NestedBlockComment = 14,
+    MultiLineLineComment = 15,
+    UnknownEscape = 16,
+    EscapeOutOfRange = 17,
+    MissingHexDigits = 18,
+    IncompleteUcn = 19,
+    InvalidUcn = 20,
+    ExpectedEscapeBrace = 21,
+    EmptyDelimitedEscape = 22,
+    UnterminatedDelimitedEscape = 23,
+    UnknownCharName = 24,
+    InvalidUcnInIdentifier = 25,
+    InvalidEncoding = 26,
+}
+
+impl DiagCode {
+    /// The number shown when the diagnostic is displayed, i.e. `L0004`.
+    #[must_use]
+    pub const fn number(self) -> u16 {
+        self as u16
+    }
+
+    #[must_use]
+    pub const fn severity(self) -> Severity {
+        match self {
+            DiagCode::NestedBlockComment
+            | DiagCode::MultiLineLineComment
+            | DiagCode::UnknownEscape => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            DiagCode::UnknownChar => "unknown character in source",
+            DiagCode::StrayBackSlash => "stray '\\' in source",
+            DiagCode::StrayNumPrefix => "numeric prefix without any digits",
+            DiagCode::UnterminatedBlockComment => {
+                "unterminated block comment starting here"
+            }
+            DiagCode::UnterminatedStr => "missing terminating '\"' character",
+            DiagCode::UnterminatedCharSeq => {
+                "missing terminating '\\'' character"
+            }
+            DiagCode::UnterminatedHeader => "unterminated header name",
+            DiagCode::RawStrNotDChar => {
+                "invalid character in raw string delimiter"
+            }
+            DiagCode::RawStrPrefixMultiChar => {
+                "raw string delimiters must repeat a single character"
+            }
+            DiagCode::RawStrPrefixTooLong => {
+                "raw string delimiter exceeds 16 characters"
+            }
+            DiagCode::RawStrUnterminated => "unterminated raw string",
+            DiagCode::RawStrUnterminatedInPrefix => {
+                "unterminated raw string delimiter"
+            }
+            DiagCode::RawStrUnterminatedInSuffix => {
+                "unterminated raw string closing delimiter"
+            }
+            DiagCode::NestedBlockComment => "'/*' within block comment",
+            DiagCode::MultiLineLineComment => "multi-line '//' comment",
+            DiagCode::UnknownEscape => "unknown escape sequence",
+            DiagCode::EscapeOutOfRange => "escape sequence out of range",
+            DiagCode::MissingHexDigits => {
+                "'\\x' used with no following hex digits"
+            }
+            DiagCode::IncompleteUcn => "incomplete universal character name",
+            DiagCode::InvalidUcn => {
+                "universal character name refers to an invalid character"
+            }
+            DiagCode::ExpectedEscapeBrace => {
+                "expected '{' after '\\N' escape sequence"
+            }
+            DiagCode::EmptyDelimitedEscape => {
+                "delimited escape sequence with empty braces"
+            }
+            DiagCode::UnterminatedDelimitedEscape => {
+                "unterminated delimited escape sequence"
+            }
+            DiagCode::UnknownCharName => "unknown character name",
+            DiagCode::InvalidUcnInIdentifier => {
+                "universal character name is not valid in an identifier"
+            }
+            DiagCode::InvalidEncoding => {
+                "invalid byte sequence for the input encoding"
+            }
+        }
+    }
+}
+
+impl From<RawStrErr> for DiagCode {
+    fn from(err: RawStrErr) -> DiagCode {
+        match err {
+            RawStrErr::NotDChar => DiagCode::RawStrNotDChar,
+            RawStrErr::PrefixMultiChar => DiagCode::RawStrPrefixMultiChar,
+            RawStrErr::PrefixTooLong => DiagCode::RawStrPrefixTooLong,
+            RawStrErr::Unterminated => DiagCode::RawStrUnterminated,
+            RawStrErr::UnterminatedInPrefix => {
+                DiagCode::RawStrUnterminatedInPrefix
+            }
+            RawStrErr::UnterminatedInSuffix => {
+                DiagCode::RawStrUnterminatedInSuffix
+            }
+        }
+    }
+}
+
+/// Half open range of byte offsets into the source.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Span {
+    start: u32,
+    end: u32,
+}
+
+impl Span {
+    #[must_use]
+    pub const fn new(start: u32, end: u32) -> Span {
+        debug_assert!(start <= end);
+        Span { start, end }
+    }
+
+    #[must_use]
+    pub const fn start(self) -> u32 {
+        self.start
+    }
+
+    #[must_use]
+    pub const fn end(self) -> u32 {
+        self.end
+    }
+
+    #[must_use]
+    pub const fn len(self) -> u32 {
+        self.end - self.start
+    }
+
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// A problem found while lexing along with the source it concerns.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    code: DiagCode,
+    span: Span,
+}
+
+impl Diagnostic {
+    #[must_use]
+    pub const fn new(code: DiagCode, span: Span) -> Diagnostic {
+        Diagnostic { code, span }
+    }
+
+    #[must_use]
+    pub const fn code(self) -> DiagCode {
+        self.code
+    }
+
+    #[must_use]
+    pub const fn severity(self) -> Severity {
+        self.code.severity()
+    }
+
+    #[must_use]
+    pub const fn span(self) -> Span {
+        self.span
+    }
+
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        self.code.message()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[L{:04}]: {}",
+            self.severity().as_str(),
+            self.code.number(),
+            self.message()
+        )
+    }
+}
+
+/// Receives the diagnostics reported by a [`Cursor`](crate::Cursor).
+pub trait DiagSink {
+    fn report(&mut self, diag: Diagnostic);
+}
+
+impl<F: FnMut(Diagnostic)> DiagSink for F {
+    fn report(&mut self, diag: Diagnostic) {
+        self(diag);
+    }
+}
@@ -0,0 +1,272 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+use crate::{Cursor, DiagCode, DiagSink, Diagnostic, Span};
+
+use clowncc_version::LangOptions;
+
+/// Encoding of the raw bytes of a source file.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InputCharset {
+    /// UTF-8, with or without a byte order mark.
+    Utf8,
+    /// ISO 8859-1, where every byte is the code point of the same value.
+    Latin1,
+    /// UTF-16 with the byte order given by its byte order mark, or big endian
+    /// if it has none.
+    Utf16,
+}
+
+impl InputCharset {
+    /// Bytes of input for each byte of UTF-8 while the source is ASCII.
+    const fn ascii_unit(self) -> u32 {
+        match self {
+            InputCharset::Utf8 | InputCharset::Latin1 => 1,
+            InputCharset::Utf16 => 2,
+        }
+    }
+}
+
+/// Decodes the raw bytes of a source into a [`SourceInput`] which can be
+/// tokenized by a [`Cursor`].
+///
+/// Bytes which are invalid in the charset are replaced by U+FFFD and reported
+/// with their span in the raw bytes.
+pub struct InputDecoder<'sink> {
+    charset: InputCharset,
+    diag_sink: Option<&'sink mut dyn DiagSink>,
+}
+
+impl<'sink> InputDecoder<'sink> {
+    #[must_use]
+    pub fn new(charset: InputCharset) -> InputDecoder<'sink> {
+        InputDecoder {
+            charset,
+            diag_sink: None,
+        }
+    }
+
+    /// Reports invalid byte sequences to `sink`.
+    #[must_use]
+    pub fn with_diag_sink(
+        mut self,
+        sink: &'sink mut dyn DiagSink,
+    ) -> InputDecoder<'sink> {
+        self.diag_sink = Some(sink);
+        self
+    }
+
+    #[must_use]
+    pub fn decode(mut self, bytes: &[u8]) -> SourceInput<'_> {
+        match self.charset {
+            InputCharset::Utf8 => self.decode_utf8(bytes),
+            InputCharset::Latin1 => self.decode_latin1(bytes),
+            InputCharset::Utf16 => self.decode_utf16(bytes),
+        }
+    }
+
+    fn report(&mut self, start: usize, len: usize) {
+        if let Some(sink) = self.diag_sink.as_deref_mut() {
+            let span = Span::new(start as u32, (start + len) as u32);
+            sink.report(Diagnostic::new(DiagCode::InvalidEncoding, span));
+        }
+    }
+
+    fn decode_utf8<'bytes>(
+        &mut self,
+        bytes: &'bytes [u8],
+    ) -> SourceInput<'bytes> {
+        let (bom_len, bytes) = match bytes {
+            [0xEF, 0xBB, 0xBF, rest @ ..] => (3, rest),
+            _ => (0, bytes),
+        };
+        let mut builder = Builder::new(self.charset, bom_len);
+        if let Ok(text) = core::str::from_utf8(bytes) {
+            return builder.finish(Cow::Borrowed(text));
+        }
+        let mut start = bom_len as usize;
+        for chunk in bytes.utf8_chunks() {
+            builder.text.push_str(chunk.valid());
+            start += chunk.valid().len();
+            let len = chunk.invalid().len();
+            if len != 0 {
+                self.report(start, len);
+                builder.push_mapped(char::REPLACEMENT_CHARACTER, len);
+                start += len;
+            }
+        }
+        let text = core::mem::take(&mut builder.text);
+        builder.finish(Cow::Owned(text))
+    }
+
+    fn decode_latin1<'bytes>(
+        &mut self,
+        bytes: &'bytes [u8],
+    ) -> SourceInput<'bytes> {
+        let mut builder = Builder::new(self.charset, 0);
+        // Only ASCII reads the same in Latin-1 and UTF-8
+        match core::str::from_utf8(bytes) {
+            Ok(text) if text.is_ascii() => {
+                return builder.finish(Cow::Borrowed(text));
+            }
+            _ => {}
+        }
+        builder.text.reserve(bytes.len() + bytes.len() / 2);
+        for &b in bytes {
+            builder.push_mapped(char::from(b), 1);
+        }
+        let text = core::mem::take(&mut builder.text);
+        builder.finish(Cow::Owned(text))
+    }
+
+    fn decode_utf16<'bytes>(
+        &mut self,
+        bytes: &'bytes [u8],
+    ) -> SourceInput<'bytes> {
+        let (bom_len, little_endian, bytes) = match bytes {
+            [0xFF, 0xFE, rest @ ..] => (2, true, rest),
+            [0xFE, 0xFF, rest @ ..] => (2, false, rest),
+            _ => (0, false, bytes),
+        };
+        let mut builder = Builder::new(self.charset, bom_len);
+        builder.text.reserve(bytes.len() / 2);
+        let units = bytes.chunks_exact(2).map(|pair| {
+            let pair = [pair[0], pair[1]];
+            if little_endian {
+                u16::from_le_bytes(pair)
+            } else {
+                u16::from_be_bytes(pair)
+            }
+        });
+        let mut offset = bom_len as usize;
+        for c in char::decode_utf16(units) {
+            let (c, len) = match c {
+                Ok(c) => (c, 2 * c.len_utf16()),
+                Err(_) => {
+                    self.report(offset, 2);
+                    (char::REPLACEMENT_CHARACTER, 2)
+                }
+            };
+            builder.push_mapped(c, len);
+            offset += len;
+        }
+        if bytes.len() % 2 == 1 {
+            self.report(offset, 1);
+            builder.push_mapped(char::REPLACEMENT_CHARACTER, 1);
+        }
+        let text = core::mem::take(&mut builder.text);
+        builder.finish(Cow::Owned(text))
+    }
+}
+
+/// Accumulates decoded text along with the points where the offsets in the
+/// text stop lining up with the offsets in the raw bytes.
+struct Builder {
+    charset: InputCharset,
+    text: String,
+    bom_len: u32,
+    checkpoints: Vec<(u32, u32)>,
+}
+
+impl Builder {
+    fn new(charset: InputCharset, bom_len: u32) -> Builder {
+        Builder {
+            charset,
+            text: String::new(),
+            bom_len,
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Appends a character decoded from `input_len` bytes of input.
+    fn push_mapped(&mut self, c: char, input_len: usize) {
+        let unit = self.charset.ascii_unit();
+        let start = self.text.len() as u32;
+        self.text.push(c);
+        let text_len = self.text.len() as u32 - start;
+        if input_len as u32 != unit * text_len {
+            let input_start = self.input_offset(start);
+            let checkpoint = (start + text_len, input_start + input_len as u32);
+            self.checkpoints.push(checkpoint);
+        }
+    }
+
+    fn input_offset(&self, text_offset: u32) -> u32 {
+        input_offset(self.charset, self.bom_len, &self.checkpoints, text_offset)
+    }
+
+    fn finish(self, text: Cow<'_, str>) -> SourceInput<'_> {
+        SourceInput {
+            text,
+            charset: self.charset,
+            bom_len: self.bom_len,
+            checkpoints: self.checkpoints,
+        }
+    }
+}
+
+fn input_offset(
+    charset: InputCharset,
+    bom_len: u32,
+    checkpoints: &[(u32, u32)],
+    text_offset: u32,
+) -> u32 {
+    let idx = checkpoints.partition_point(|&(text, _)| text <= text_offset);
+    let (text, input) = match idx.checked_sub(1) {
+        Some(idx) => checkpoints[idx],
+        None => (0, bom_len),
+    };
+    input + (text_offset - text) * charset.ascii_unit()
+}
+
+/// Source text decoded by an [`InputDecoder`] which remembers how to map
+/// offsets in the text back to offsets in the raw bytes.
+pub struct SourceInput<'bytes> {
+    text: Cow<'bytes, str>,
+    charset: InputCharset,
+    bom_len: u32,
+    checkpoints: Vec<(u32, u32)>,
+}
+
+impl SourceInput<'_> {
+    /// The decoded source, which borrows the raw bytes when they were already
+    /// valid UTF-8.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    #[must_use]
+    pub const fn charset(&self) -> InputCharset {
+        self.charset
+    }
+
+    /// Length of the byte order mark which was stripped, if any.
+    #[must_use]
+    pub const fn bom_len(&self) -> u32 {
+        self.bom_len
+    }
+
+    /// A cursor over the decoded source.
+    #[must_use]
+    pub fn cursor(&self, lang_opts: impl Into<LangOptions>) -> Cursor<'_> {
+        Cursor::new(&self.text, lang_opts)
+    }
+
+    /// Maps an offset in the decoded text, which must be on a char boundary,
+    /// to the offset of the same character in the raw bytes.
+    #[must_use]
+    pub fn input_offset(&self, text_offset: u32) -> u32 {
+        debug_assert!(self.text.is_char_boundary(text_offset as usize));
+        input_offset(self.charset, self.bom_len, &self.checkpoints, text_offset)
+    }
+
+    /// Maps a span of the decoded text, like the span of a token or
+    /// [`Diagnostic`], to the span of the same characters in the raw bytes.
+    #[must_use]
+    pub fn input_span(&self, span: Span) -> Span {
+        Span::new(
+            self.input_offset(span.start()),
+            self.input_offset(span.end()),
+        )
+    }
+}
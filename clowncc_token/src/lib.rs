@@ -3,6 +3,21 @@
 //! `clowncc_token` aims to provide simple lexer that can separate out tokens
 //! from source code in a minimal representation. A cursor [`Cursor`] can
 //! iterate over source code [`&str`] to generate small tokens [`Token`].
+//! Tokens only know their length, so [`PosCursor`] can wrap a cursor to pair
+//! each token with its [`SourcePos`] and [`Tokens`] pairs each token with its
+//! source text. [`ContextTokens`] additionally lexes header-names wherever a
+//! directive or import allows them. A whole source can be tokenized into a
+//! reusable [`TokenBuffer`] for later passes and updated after a [`TextEdit`]
+//! by lexing only the tokens around it. [`TokenPrinter`] prints tokens back
+//! either exactly as written or in a canonical form, and [`needs_separator`]
+//! tells where printed tokens need a space between them. The value of string
+//! and character literals is available through [`LitDecoder`], and
+//! [`collect_comments`] finds the cleaned up text of comments along with the
+//! tokens doc comments document.
+//!
+//! Sources which are not UTF-8 text, or which start with a byte order mark,
+//! are decoded from raw bytes by an [`InputDecoder`] into a [`SourceInput`]
+//! which maps spans of the decoded text back to the raw bytes.
 //!
 //! # Errors
 //!
@@ -11,15 +26,45 @@
 //!
 //! Delimited tokens (`BlockComment`, `SystemHeader`, `Header`, `Str`,
 //! `CharSeq`) may be unterminated but contain a flag indicating this error.
+//!
+//! A [`DiagSink`] given to the cursor additionally receives a [`Diagnostic`]
+//! with a code, span and message for each of these errors as well as for
+//! warnings about otherwise valid tokens.
 #![no_std]
 
+extern crate alloc;
+
 mod char_info;
+mod comments;
+mod context;
 mod cursor;
+mod diagnostic;
+mod input;
+mod literal;
+mod position;
+mod printer;
+mod source_chars;
+mod stream;
 mod token;
+mod token_buffer;
 
 #[cfg(test)]
 mod tests;
 
 pub use char_info::CharInfo;
+pub use comments::{collect_comments, Comment, CommentStyle};
+pub use context::ContextTokens;
 pub use cursor::Cursor;
-pub use token::{DCharSeq, LitType, NumberBase, RawStrErr, Token, TokenKind};
+pub use diagnostic::{DiagCode, DiagSink, Diagnostic, Severity, Span};
+pub use input::{InputCharset, InputDecoder, SourceInput};
+pub use literal::{unicode_char_name, LitDecoder};
+pub use position::{PosCursor, SourcePos};
+pub use printer::{
+    needs_separator, print_tokens, write_canonical, PrintMode, TokenPrinter,
+};
+pub use stream::Tokens;
+pub use token::{
+    DCharSeq, LitType, NumberBase, NumberInfo, RawStrErr, Token, TokenFlags,
+    TokenKind,
+};
+pub use token_buffer::{TextEdit, TokenBuffer};
@@ -0,0 +1,436 @@
+use clowncc_version::{LangOptions, StdVersion, TargetInfo};
+
+use crate::{
+    source_chars::SourceChars, DiagCode, Diagnostic, LitType, NumberBase, Span,
+};
+
+/// Decodes the body of a string or character literal into the code units of
+/// its encoding, i.e. UTF-8 bytes for narrow literals and UTF-16 code units
+/// for `u` literals.
+///
+/// The text is the full source of a terminated `Str`, `CharSeq` or `RawStr`
+/// token including its prefix and any ud-suffix. Escape sequences and line
+/// splices are processed except in raw strings.
+///
+/// Problems with escape sequences are yielded in place of the code units the
+/// escape would produce. Spans are byte offsets into the token text.
+///
+/// Literals lexed with trigraph replacement must be decoded with the same
+/// [`LangOptions`].
+#[derive(Clone)]
+pub struct LitDecoder<'text> {
+    text: &'text str,
+    chars: SourceChars<'text>,
+    body_end: usize,
+    lit_type: LitType,
+    std_vers: StdVersion,
+    is_raw: bool,
+    wchar_bits: u32,
+    name_lookup: fn(&str) -> Option<char>,
+    /// Remaining code units of the last character, in reverse order.
+    pending: [u32; 4],
+    pending_len: usize,
+}
+
+impl<'text> LitDecoder<'text> {
+    #[must_use]
+    pub fn new(
+        text: &'text str,
+        lit_type: LitType,
+        lang_opts: impl Into<LangOptions>,
+    ) -> LitDecoder<'text> {
+        let lang_opts = lang_opts.into();
+        let open = text.find(['"', '\'']).unwrap_or(text.len());
+        let is_raw = open > 0 && text.as_bytes()[open - 1] == b'R';
+        let (body_start, body_end) = if is_raw {
+            raw_body_bounds(text, open)
+        } else {
+            quoted_body_bounds(text, open)
+        };
+        let mut chars = SourceChars::new(&text[body_start..body_end]);
+        chars.set_trigraphs(lang_opts.has_trigraphs() && !is_raw);
+        LitDecoder {
+            text,
+            chars,
+            body_end,
+            lit_type,
+            std_vers: lang_opts.std_version(),
+            is_raw,
+            wchar_bits: 32,
+            name_lookup: unicode_char_name,
+            pending: [0; 4],
+            pending_len: 0,
+        }
+    }
+
+    /// Decode wide literals as the `wchar_t` of the target, which is UTF-16
+    /// when it is 16 bits wide. Defaults to a 32-bit `wchar_t`.
+    #[must_use]
+    pub fn with_target(mut self, target: &TargetInfo) -> LitDecoder<'text> {
+        self.wchar_bits = target.wchar_width();
+        self
+    }
+
+    /// Replace the lookup used for `\N{name}` escapes, which defaults to
+    /// [`unicode_char_name`].
+    #[must_use]
+    pub fn with_name_lookup(
+        mut self,
+        lookup: fn(&str) -> Option<char>,
+    ) -> LitDecoder<'text> {
+        self.name_lookup = lookup;
+        self
+    }
+
+    #[must_use]
+    fn offset(&self) -> u32 {
+        (self.body_end - self.chars.as_str().len()) as u32
+    }
+
+    #[must_use]
+    fn peek(&self) -> Option<char> {
+        next_spliced(&mut self.chars.clone(), self.is_raw)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        next_spliced(&mut self.chars, self.is_raw)
+    }
+
+    #[must_use]
+    fn diag(&self, code: DiagCode, start: u32) -> Diagnostic {
+        Diagnostic::new(code, Span::new(start, self.offset()))
+    }
+
+    fn pop_pending(&mut self) -> Option<u32> {
+        self.pending_len = self.pending_len.checked_sub(1)?;
+        Some(self.pending[self.pending_len])
+    }
+
+    /// Width of a code unit of the literal's encoding on the target.
+    #[must_use]
+    fn unit_bits(&self) -> u32 {
+        match self.lit_type {
+            LitType::Wide => self.wchar_bits,
+            lit_type => lit_type.code_unit_bits(),
+        }
+    }
+
+    /// Queues the code units encoding a character.
+    fn queue(&mut self, c: char) {
+        let mut units = [0; 4];
+        let len = match self.unit_bits() {
+            8 => {
+                let mut bytes = [0; 4];
+                let len = c.encode_utf8(&mut bytes).len();
+                for (unit, byte) in units.iter_mut().zip(bytes) {
+                    *unit = byte.into();
+                }
+                len
+            }
+            16 => {
+                let mut wide = [0; 2];
+                let len = c.encode_utf16(&mut wide).len();
+                for (unit, wide) in units.iter_mut().zip(wide) {
+                    *unit = wide.into();
+                }
+                len
+            }
+            _ => {
+                units[0] = c.into();
+                1
+            }
+        };
+        for &unit in units[..len].iter().rev() {
+            self.pending[self.pending_len] = unit;
+            self.pending_len += 1;
+        }
+    }
+
+    fn encode(&mut self, c: char) -> u32 {
+        self.queue(c);
+        self.pop_pending()
+            .expect("Characters have at least one code unit")
+    }
+
+    #[must_use]
+    fn has_ucn(&self) -> bool {
+        self.std_vers.is_cpp() || self.std_vers.is_since_c99()
+    }
+
+    /// Delimited escapes `\o{}`, `\x{}`, `\u{}` and named escapes `\N{}` were
+    /// introduced in C++23.
+    #[must_use]
+    fn has_delimited_esc(&self) -> bool {
+        self.std_vers.is_since_cpp23()
+    }
+
+    fn eat_escape(&mut self, start: u32) -> Result<u32, Diagnostic> {
+        let Some(c) = self.bump() else {
+            self.queue('\\');
+            return Err(self.diag(DiagCode::UnknownEscape, start));
+        };
+        let delimited = self.has_delimited_esc() && self.peek() == Some('{');
+        Ok(match c {
+            '\'' | '"' | '?' | '\\' => c.into(),
+            'a' => 0x07,
+            'b' => 0x08,
+            'f' => 0x0C,
+            'n' => 0x0A,
+            'r' => 0x0D,
+            't' => 0x09,
+            'v' => 0x0B,
+            '0'..='7' => return self.eat_octal(c, start),
+            'o' if delimited => {
+                let value = self.eat_delimited(NumberBase::Octal, start)?;
+                return self.check_unit(value, start);
+            }
+            'x' if delimited => {
+                let value =
+                    self.eat_delimited(NumberBase::Hexidecimal, start)?;
+                return self.check_unit(value, start);
+            }
+            'x' => return self.eat_hex(start),
+            'u' if delimited => {
+                let value =
+                    self.eat_delimited(NumberBase::Hexidecimal, start)?;
+                return self.check_ucn(value, start);
+            }
+            'u' | 'U' if self.has_ucn() => {
+                return self.eat_ucn(if c == 'u' { 4 } else { 8 }, start);
+            }
+            'N' if self.has_delimited_esc() => return self.eat_named(start),
+            _ => {
+                self.queue(c);
+                return Err(self.diag(DiagCode::UnknownEscape, start));
+            }
+        })
+    }
+
+    /// Adds a digit to an escape's value, [`None`] marking overflow.
+    #[must_use]
+    fn push_digit(value: Option<u32>, base: u32, c: char) -> Option<u32> {
+        let digit = c.to_digit(base).expect("Digit was already matched");
+        value?.checked_mul(base)?.checked_add(digit)
+    }
+
+    fn check_unit(
+        &self,
+        value: Option<u32>,
+        start: u32,
+    ) -> Result<u32, Diagnostic> {
+        let bits = self.unit_bits();
+        match value {
+            Some(value) if bits == 32 || value >> bits == 0 => Ok(value),
+            Some(_) | None => Err(self.diag(DiagCode::EscapeOutOfRange, start)),
+        }
+    }
+
+    fn eat_octal(
+        &mut self,
+        first: char,
+        start: u32,
+    ) -> Result<u32, Diagnostic> {
+        let mut value = Self::push_digit(Some(0), 8, first);
+        for _ in 0..2 {
+            match self.peek() {
+                Some(c) if NumberBase::Octal.matches(c) => {
+                    self.bump();
+                    value = Self::push_digit(value, 8, c);
+                }
+                Some(_) | None => break,
+            }
+        }
+        self.check_unit(value, start)
+    }
+
+    fn eat_hex(&mut self, start: u32) -> Result<u32, Diagnostic> {
+        let mut value = Some(0);
+        let mut has_digits = false;
+        while let Some(c) = self.peek() {
+            if !NumberBase::Hexidecimal.matches(c) {
+                break;
+            }
+            self.bump();
+            value = Self::push_digit(value, 16, c);
+            has_digits = true;
+        }
+        if !has_digits {
+            return Err(self.diag(DiagCode::MissingHexDigits, start));
+        }
+        self.check_unit(value, start)
+    }
+
+    /// Eats the braces and digits of a delimited escape. The value is
+    /// [`None`] if it overflowed.
+    fn eat_delimited(
+        &mut self,
+        base: NumberBase,
+        start: u32,
+    ) -> Result<Option<u32>, Diagnostic> {
+        let radix = match base {
+            NumberBase::Octal => 8,
+            _ => 16,
+        };
+        debug_assert!(self.peek() == Some('{'));
+        self.bump();
+        let mut value = Some(0);
+        let mut has_digits = false;
+        loop {
+            match self.peek() {
+                Some('}') => break,
+                Some(c) if base.matches(c) => {
+                    self.bump();
+                    value = Self::push_digit(value, radix, c);
+                    has_digits = true;
+                }
+                Some(_) | None => {
+                    let code = DiagCode::UnterminatedDelimitedEscape;
+                    return Err(self.diag(code, start));
+                }
+            }
+        }
+        self.bump();
+        if !has_digits {
+            return Err(self.diag(DiagCode::EmptyDelimitedEscape, start));
+        }
+        Ok(value)
+    }
+
+    fn eat_ucn(&mut self, count: u32, start: u32) -> Result<u32, Diagnostic> {
+        let mut value = Some(0);
+        for _ in 0..count {
+            match self.peek() {
+                Some(c) if NumberBase::Hexidecimal.matches(c) => {
+                    self.bump();
+                    value = Self::push_digit(value, 16, c);
+                }
+                Some(_) | None => {
+                    return Err(self.diag(DiagCode::IncompleteUcn, start));
+                }
+            }
+        }
+        self.check_ucn(value, start)
+    }
+
+    /// C forbids universal character names for the basic character set and
+    /// control characters, other than `$`, `@` and `` ` ``.
+    fn check_ucn(
+        &mut self,
+        value: Option<u32>,
+        start: u32,
+    ) -> Result<u32, Diagnostic> {
+        let c = value.and_then(char::from_u32).filter(|&c| {
+            !self.std_vers.is_c()
+                || c >= '\u{A0}'
+                || matches!(c, '$' | '@' | '`')
+        });
+        match c {
+            Some(c) => Ok(self.encode(c)),
+            None => Err(self.diag(DiagCode::InvalidUcn, start)),
+        }
+    }
+
+    fn eat_named(&mut self, start: u32) -> Result<u32, Diagnostic> {
+        if self.peek() != Some('{') {
+            return Err(self.diag(DiagCode::ExpectedEscapeBrace, start));
+        }
+        self.bump();
+        let name_start = self.offset() as usize;
+        loop {
+            match self.peek() {
+                Some('}') => break,
+                Some(c)
+                    if c.is_ascii_uppercase()
+                        || c.is_ascii_digit()
+                        || matches!(c, ' ' | '-') =>
+                {
+                    self.bump();
+                }
+                Some(_) | None => {
+                    let code = DiagCode::UnterminatedDelimitedEscape;
+                    return Err(self.diag(code, start));
+                }
+            }
+        }
+        let name = &self.text[name_start..self.offset() as usize];
+        self.bump();
+        if name.is_empty() {
+            return Err(self.diag(DiagCode::EmptyDelimitedEscape, start));
+        }
+        match (self.name_lookup)(name) {
+            Some(c) => Ok(self.encode(c)),
+            None => Err(self.diag(DiagCode::UnknownCharName, start)),
+        }
+    }
+}
+
+impl Iterator for LitDecoder<'_> {
+    type Item = Result<u32, Diagnostic>;
+
+    fn next(&mut self) -> Option<Result<u32, Diagnostic>> {
+        if let Some(unit) = self.pop_pending() {
+            return Some(Ok(unit));
+        }
+        let start = self.offset();
+        match self.bump()? {
+            '\\' if !self.is_raw => Some(self.eat_escape(start)),
+            c => Some(Ok(self.encode(c))),
+        }
+    }
+}
+
+/// Body of a quoted literal between its quotes. Unterminated literals run to
+/// the end of the line.
+fn quoted_body_bounds(text: &str, open: usize) -> (usize, usize) {
+    let Some(quote) = text[open..].chars().next() else {
+        return (open, open);
+    };
+    let body_start = open + 1;
+    match text.rfind(quote) {
+        Some(close) if close >= body_start => (body_start, close),
+        Some(_) | None => (
+            body_start,
+            body_start.max(text.trim_end_matches('\n').len()),
+        ),
+    }
+}
+
+/// Body of a raw string between `delim(` and `)delim`.
+fn raw_body_bounds(text: &str, open: usize) -> (usize, usize) {
+    let body_start =
+        text[open..].find('(').map_or(text.len(), |i| open + i + 1);
+    let delim_len = body_start.saturating_sub(open + 2);
+    let body_end = text
+        .rfind('"')
+        .and_then(|close| close.checked_sub(delim_len + 1))
+        .filter(|&end| end >= body_start)
+        .unwrap_or(body_start);
+    (body_start, body_end)
+}
+
+/// Next character after removing line splices, which are not processed in raw
+/// strings.
+fn next_spliced(chars: &mut SourceChars<'_>, is_raw: bool) -> Option<char> {
+    loop {
+        let c = chars.next()?;
+        if c != '\\' || is_raw {
+            return Some(c);
+        }
+        let mut dup = chars.clone();
+        loop {
+            match dup.next() {
+                Some('\n') => break,
+                Some(c) if c.is_whitespace() => {}
+                Some(_) | None => return Some('\\'),
+            }
+        }
+        *chars = dup;
+    }
+}
+
+/// Looks up any Unicode character name or alias for `\N{name}` escapes, i.e.
+/// `LATIN SMALL LETTER E WITH ACUTE` or the control alias `LINE FEED`.
+#[must_use]
+pub fn unicode_char_name(name: &str) -> Option<char> {
+    unicode_names2::character(name)
+}
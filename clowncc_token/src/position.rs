@@ -0,0 +1,114 @@
+use core::fmt;
+
+use crate::{Cursor, Token};
+
+/// Location of the first character of a token.
+///
+/// The offset is in bytes while lines and columns are 1-based and count
+/// physical lines and characters, so an escaped newline inside a token still
+/// moves the following tokens onto the next line.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SourcePos {
+    offset: u32,
+    line: u32,
+    column: u32,
+}
+
+impl SourcePos {
+    /// The position of the first character in a source.
+    pub const START: SourcePos = SourcePos {
+        offset: 0,
+        line: 1,
+        column: 1,
+    };
+
+    #[must_use]
+    pub const fn offset(self) -> u32 {
+        self.offset
+    }
+
+    #[must_use]
+    pub const fn line(self) -> u32 {
+        self.line
+    }
+
+    #[must_use]
+    pub const fn column(self) -> u32 {
+        self.column
+    }
+
+    /// Moves the position past the text of a token. Lines only need to be
+    /// scanned for when the token reports containing a newline.
+    pub fn advance(&mut self, token: &Token, text: &str) {
+        debug_assert!(text.len() == token.length() as usize);
+        self.offset += token.length();
+        if !token.flags().has_new_line() {
+            self.column += char_count(text);
+            return;
+        }
+        let newlines = text.bytes().filter(|&b| b == b'\n').count();
+        let last_line = text.rsplit('\n').next().unwrap_or_default();
+        self.line += newlines as u32;
+        self.column = 1 + char_count(last_line);
+    }
+}
+
+impl fmt::Debug for SourcePos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{} @ {}", self.line, self.column, self.offset)
+    }
+}
+
+fn char_count(text: &str) -> u32 {
+    let count = if text.is_ascii() {
+        text.len()
+    } else {
+        text.chars().count()
+    };
+    count as u32
+}
+
+/// Wraps a [`Cursor`] to pair every token with the [`SourcePos`] it starts at.
+///
+/// Positions are relative to where the cursor was when it was wrapped, which
+/// is the start of the source for a fresh cursor.
+pub struct PosCursor<'chars> {
+    cursor: Cursor<'chars>,
+    code: &'chars str,
+    pos: SourcePos,
+}
+
+impl<'chars> PosCursor<'chars> {
+    #[must_use]
+    pub fn new(cursor: Cursor<'chars>) -> PosCursor<'chars> {
+        PosCursor {
+            code: cursor.as_str(),
+            cursor,
+            pos: SourcePos::START,
+        }
+    }
+
+    /// The position the next token will start at.
+    #[must_use]
+    pub const fn pos(&self) -> SourcePos {
+        self.pos
+    }
+
+    pub fn next_token(&mut self) -> Option<(Token, SourcePos)> {
+        let token = self.cursor.next_token()?;
+        Some(self.track(token))
+    }
+
+    pub fn next_token_header(&mut self) -> Option<(Token, SourcePos)> {
+        let token = self.cursor.next_token_header()?;
+        Some(self.track(token))
+    }
+
+    fn track(&mut self, token: Token) -> (Token, SourcePos) {
+        let start = self.pos;
+        let begin = start.offset as usize;
+        let text = &self.code[begin..begin + token.length() as usize];
+        self.pos.advance(&token, text);
+        (token, start)
+    }
+}
@@ -0,0 +1,197 @@
+use core::fmt;
+
+use crate::{source_chars::SourceChars, Cursor, Token, TokenKind, Tokens};
+
+use clowncc_version::LangOptions;
+
+/// How [`TokenPrinter`] spells the tokens it is given.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PrintMode {
+    /// Prints the source text of every token, reproducing the source exactly.
+    Lossless,
+    /// Prints tokens as the compiler sees them after translation phase 3 so
+    /// that sources differing only in formatting print the same.
+    ///
+    /// Trigraphs and line splices are removed from tokens other than raw
+    /// strings, punctuators use their primary spelling, and every run of
+    /// whitespace and comments becomes a single space, or a single newline if
+    /// it splits lines. Whitespace before the first token is dropped.
+    Canonical,
+}
+
+/// Separator owed before the next printed token in canonical mode.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum Separator {
+    None,
+    Space,
+    Newline,
+}
+
+/// Prints tokens along with their source text in a [`PrintMode`].
+pub struct TokenPrinter {
+    mode: PrintMode,
+    separator: Separator,
+    has_printed: bool,
+}
+
+impl TokenPrinter {
+    #[must_use]
+    pub const fn new(mode: PrintMode) -> TokenPrinter {
+        TokenPrinter {
+            mode,
+            separator: Separator::None,
+            has_printed: false,
+        }
+    }
+
+    /// Prints the next token whose source text is `text`.
+    pub fn print(
+        &mut self,
+        out: &mut impl fmt::Write,
+        token: &Token,
+        text: &str,
+    ) -> fmt::Result {
+        debug_assert!(text.len() == token.length() as usize);
+        if self.mode == PrintMode::Lossless {
+            return out.write_str(text);
+        }
+        let separator = match token.kind() {
+            TokenKind::Whitespace { splits_lines: true } => Separator::Newline,
+            // Comments are replaced by a space in translation phase 3
+            TokenKind::Whitespace {
+                splits_lines: false,
+            }
+            | TokenKind::LineComment
+            | TokenKind::BlockComment => Separator::Space,
+            _ => {
+                self.write_separator(out)?;
+                self.has_printed = true;
+                return write_canonical(out, token, text);
+            }
+        };
+        self.separator = self.separator.max(separator);
+        Ok(())
+    }
+
+    fn write_separator(&mut self, out: &mut impl fmt::Write) -> fmt::Result {
+        let separator =
+            core::mem::replace(&mut self.separator, Separator::None);
+        match separator {
+            _ if !self.has_printed => Ok(()),
+            Separator::None => Ok(()),
+            Separator::Space => out.write_char(' '),
+            Separator::Newline => out.write_char('\n'),
+        }
+    }
+
+    /// Prints what is owed after the last token, which is a newline if the
+    /// source ended with one in canonical mode.
+    pub fn finish(&mut self, out: &mut impl fmt::Write) -> fmt::Result {
+        match core::mem::replace(&mut self.separator, Separator::None) {
+            Separator::Newline if self.has_printed => out.write_char('\n'),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Prints all remaining tokens of `cursor` in `mode`.
+pub fn print_tokens(
+    out: &mut impl fmt::Write,
+    cursor: Cursor<'_>,
+    mode: PrintMode,
+) -> fmt::Result {
+    let mut printer = TokenPrinter::new(mode);
+    for (token, text) in Tokens::new(cursor) {
+        printer.print(out, &token, text)?;
+    }
+    printer.finish(out)
+}
+
+/// Prints the canonical spelling of a single token, see
+/// [`PrintMode::Canonical`]. Whitespace and comments are printed as is.
+pub fn write_canonical(
+    out: &mut impl fmt::Write,
+    token: &Token,
+    text: &str,
+) -> fmt::Result {
+    if let Some(spelling) = token.kind().spelling() {
+        return out.write_str(spelling);
+    }
+    let has_splice = token.flags().has_new_line()
+        && !token.kind().is_multi_line()
+        && text.contains('\\');
+    let is_raw = matches!(
+        token.kind(),
+        TokenKind::RawStr { .. } | TokenKind::BadRawStr(_)
+    );
+    if is_raw || !(has_splice || token.flags().has_trigraph()) {
+        return out.write_str(text);
+    }
+
+    write_without_splices(out, text, token.flags().has_trigraph())
+}
+
+/// Prints `text` without its line splices, replacing trigraphs if enabled.
+pub(crate) fn write_without_splices(
+    out: &mut impl fmt::Write,
+    text: &str,
+    trigraphs: bool,
+) -> fmt::Result {
+    let mut chars = SourceChars::new(text);
+    chars.set_trigraphs(trigraphs);
+    while let Some(c) = chars.next() {
+        if c == '\\' && skip_splice(&mut chars) {
+            continue;
+        }
+        out.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// Whether printing the texts of two adjacent tokens without a separator
+/// would lex differently, i.e. `-` then `>` would become `->` and `u8` then
+/// `"s"` would become a single string, so a space must be printed between
+/// them. Header-names are lexed as such and the rest as by
+/// [`Cursor::next_token`].
+#[must_use]
+pub fn needs_separator(
+    (left, left_text): (&Token, &str),
+    (right, right_text): (&Token, &str),
+    lang_opts: impl Into<LangOptions>,
+) -> bool {
+    debug_assert!(left_text.len() == left.length() as usize);
+    debug_assert!(right_text.len() == right.length() as usize);
+    let joined = [left_text, right_text].concat();
+    !lexes_same(&joined, [left, right], lang_opts.into())
+}
+
+/// Whether `code` lexes as exactly `tokens`.
+fn lexes_same(code: &str, tokens: [&Token; 2], lang_opts: LangOptions) -> bool {
+    let mut cursor = Cursor::new(code, lang_opts);
+    tokens.into_iter().all(|token| {
+        let relexed = match token.kind() {
+            TokenKind::SystemHeader | TokenKind::Header => {
+                cursor.next_token_header()
+            }
+            _ => cursor.next_token(),
+        };
+        relexed.is_some_and(|relexed| {
+            relexed.kind() == token.kind() && relexed.length() == token.length()
+        })
+    })
+}
+
+/// Skips the rest of a line splice whose backslash was just read.
+fn skip_splice(chars: &mut SourceChars<'_>) -> bool {
+    let mut lookahead = chars.clone();
+    loop {
+        match lookahead.next() {
+            Some('\n') => {
+                *chars = lookahead;
+                return true;
+            }
+            Some(c) if c.is_whitespace() => {}
+            Some(_) | None => return false,
+        }
+    }
+}
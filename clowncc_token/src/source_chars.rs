@@ -0,0 +1,135 @@
+use core::str::Chars;
+
+/// Maps the final character of a trigraph sequence `??x` to its replacement.
+const fn trigraph_replacement(c: u8) -> Option<char> {
+    Some(match c {
+        b'=' => '#',
+        b'/' => '\\',
+        b'\'' => '^',
+        b'(' => '[',
+        b')' => ']',
+        b'!' => '|',
+        b'<' => '{',
+        b'>' => '}',
+        b'-' => '~',
+        _ => return None,
+    })
+}
+
+/// Character iterator over the source which performs the trigraph replacement
+/// of translation phase 1 when enabled.
+#[derive(Clone)]
+pub(crate) struct SourceChars<'chars> {
+    chars: Chars<'chars>,
+    trigraphs: bool,
+}
+
+impl<'chars> SourceChars<'chars> {
+    #[must_use]
+    pub(crate) fn new(code: &'chars str) -> SourceChars<'chars> {
+        SourceChars {
+            chars: code.chars(),
+            trigraphs: false,
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn as_str(&self) -> &'chars str {
+        self.chars.as_str()
+    }
+
+    #[must_use]
+    pub(crate) fn trigraphs(&self) -> bool {
+        self.trigraphs
+    }
+
+    pub(crate) fn set_trigraphs(&mut self, enabled: bool) {
+        self.trigraphs = enabled;
+    }
+
+    /// Skips the next `len` bytes of the source without replacing trigraphs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` does not end on a char boundary.
+    pub(crate) fn skip_bytes(&mut self, len: usize) {
+        self.chars = self.chars.as_str()[len..].chars();
+    }
+
+    /// Skips ASCII characters while `predicate` accepts them, working on bytes
+    /// rather than decoding chars. Stops before any non-ASCII character and,
+    /// when trigraphs are enabled, before any `?` so [`Self::next_spelled`]
+    /// handles those.
+    ///
+    /// Returns the skipped text and the character the predicate rejected, if
+    /// that is why skipping stopped.
+    pub(crate) fn skip_ascii_while(
+        &mut self,
+        mut predicate: impl FnMut(u8) -> bool,
+    ) -> (&'chars str, Option<char>) {
+        let rest = self.chars.as_str();
+        let mut rejected = None;
+        let len = rest
+            .bytes()
+            .position(|b| {
+                if !b.is_ascii() || (b == b'?' && self.trigraphs) {
+                    return true;
+                }
+                if predicate(b) {
+                    return false;
+                }
+                rejected = Some(char::from(b));
+                true
+            })
+            .unwrap_or(rest.len());
+        // Only ASCII was skipped so the split is on a char boundary
+        let (skipped, rest) = rest.split_at(len);
+        self.chars = rest.chars();
+        (skipped, rejected)
+    }
+
+    /// Skips any line splices, a backslash followed by a newline with only
+    /// whitespace between them, which translation phase 2 removes.
+    pub(crate) fn skip_splices(&mut self) {
+        loop {
+            let mut lookahead = self.clone();
+            if lookahead.next() != Some('\\') {
+                return;
+            }
+            loop {
+                match lookahead.next() {
+                    Some('\n') => break,
+                    Some(c) if c.is_whitespace() => {}
+                    Some(_) | None => return,
+                }
+            }
+            *self = lookahead;
+        }
+    }
+
+    /// Returns the next character and whether it was spelled as a trigraph.
+    pub(crate) fn next_spelled(&mut self) -> Option<(char, bool)> {
+        let c = self.chars.next()?;
+        if c != '?' || !self.trigraphs {
+            return Some((c, false));
+        }
+        match *self.chars.as_str().as_bytes() {
+            [b'?', third, ..] => match trigraph_replacement(third) {
+                Some(replacement) => {
+                    self.chars.nth(1);
+                    Some((replacement, true))
+                }
+                None => Some((c, false)),
+            },
+            _ => Some((c, false)),
+        }
+    }
+}
+
+impl Iterator for SourceChars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        self.next_spelled().map(|(c, _)| c)
+    }
+}
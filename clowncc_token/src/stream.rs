@@ -0,0 +1,67 @@
+use core::iter::FusedIterator;
+
+use crate::{Cursor, Span, Token};
+
+/// Wraps a [`Cursor`] to pair every token with the source text it spans.
+///
+/// Offsets and spans are relative to where the cursor was when it was wrapped,
+/// which is the start of the source for a fresh cursor.
+pub struct Tokens<'chars> {
+    cursor: Cursor<'chars>,
+    code: &'chars str,
+    offset: u32,
+}
+
+impl<'chars> Tokens<'chars> {
+    #[must_use]
+    pub fn new(cursor: Cursor<'chars>) -> Tokens<'chars> {
+        Tokens {
+            code: cursor.as_str(),
+            cursor,
+            offset: 0,
+        }
+    }
+
+    /// The byte offset the next token will start at.
+    #[must_use]
+    pub const fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// The source which has not been tokenized yet.
+    #[must_use]
+    pub fn remaining(&self) -> &'chars str {
+        &self.code[self.offset as usize..]
+    }
+
+    /// Like [`Tokens::next`] but lexes a header name if one is next, see
+    /// [`Cursor::next_token_header`].
+    pub fn next_header(&mut self) -> Option<(Token, &'chars str)> {
+        let token = self.cursor.next_token_header()?;
+        Some(self.slice(token))
+    }
+
+    /// Like [`Tokens::next`] but pairs the token with its [`Span`] instead.
+    pub fn next_spanned(&mut self) -> Option<(Token, Span)> {
+        let start = self.offset;
+        let (token, _) = self.next()?;
+        Some((token, Span::new(start, self.offset)))
+    }
+
+    fn slice(&mut self, token: Token) -> (Token, &'chars str) {
+        let start = self.offset as usize;
+        self.offset += token.length();
+        (token, &self.code[start..self.offset as usize])
+    }
+}
+
+impl<'chars> Iterator for Tokens<'chars> {
+    type Item = (Token, &'chars str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.cursor.next_token()?;
+        Some(self.slice(token))
+    }
+}
+
+impl FusedIterator for Tokens<'_> {}
@@ -14,7 +14,7 @@ pub enum TokenKind {
 
     Identifier { has_univ_char: bool },
     Whitespace { splits_lines: bool },
-    Number { base: NumberBase, has_sep: bool },
+    Number(NumberInfo),
 
     SystemHeader,
     Header,
@@ -22,6 +22,36 @@ pub enum TokenKind {
     Str { lit_type: LitType, has_esc: bool },
     RawStr { lit_type: LitType, delim: DCharSeq },
 
+    // Multi-char punctuators:
+    PoundPound,
+    AmpersandAmpersand,
+    AmpersandEqual,
+    PipePipe,
+    PipeEqual,
+    DotStar,
+    Ellipsis,
+    ColonColon,
+    EqualEqual,
+    PlusPlus,
+    PlusEqual,
+    MinusMinus,
+    MinusEqual,
+    Arrow,
+    ArrowStar,
+    StarEqual,
+    SlashEqual,
+    PercentEqual,
+    ExclamationEqual,
+    CaretEqual,
+    CaretCaret,
+    GreaterThanEqual,
+    GreaterThanGreaterThan,
+    GreaterThanGreaterThanEqual,
+    LessThanEqual,
+    LessThanLessThan,
+    LessThanLessThanEqual,
+    Spaceship,
+
     // One-char tokens:
     SemiColon,
     Pound,
@@ -65,6 +95,46 @@ impl TokenKind {
         )
     }
 
+    /// Indicates whitespace and comments, which separate tokens but are not
+    /// preprocessing-tokens themselves.
+    #[must_use]
+    pub const fn is_trivia(self) -> bool {
+        matches!(
+            self,
+            TK::Whitespace { .. } | TK::LineComment | TK::BlockComment
+        )
+    }
+
+    /// Indicates numbers, character literals and string literals, including
+    /// raw and user-defined ones.
+    #[must_use]
+    pub const fn is_literal(self) -> bool {
+        matches!(
+            self,
+            TK::Number(_)
+                | TK::CharSeq { .. }
+                | TK::Str { .. }
+                | TK::RawStr { .. }
+        )
+    }
+
+    #[must_use]
+    pub const fn is_punctuator(self) -> bool {
+        self.punctuator_spelling().is_some()
+    }
+
+    /// Indicates a kind of preprocessing-token: a header-name, identifier,
+    /// pp-number, character or string literal, or punctuator. Trivia and
+    /// error tokens are not. An unterminated literal or header-name still has
+    /// one of these kinds, so use [`Token::is_pp_token`] to also check that
+    /// it is terminated.
+    #[must_use]
+    pub const fn is_pp_token(self) -> bool {
+        matches!(self, TK::SystemHeader | TK::Header | TK::Identifier { .. })
+            || self.is_literal()
+            || self.is_punctuator()
+    }
+
     /// Indicates whether the token type can span multiple lines without escaped
     /// newlines. If a token is not multi line but contains a newline then it
     /// requires preprocessing.
@@ -97,6 +167,34 @@ impl TokenKind {
                 | TK::CharSeq { .. }
                 | TK::Str { .. }
                 | TK::RawStr { .. }
+                | TK::PoundPound
+                | TK::AmpersandAmpersand
+                | TK::AmpersandEqual
+                | TK::PipePipe
+                | TK::PipeEqual
+                | TK::DotStar
+                | TK::Ellipsis
+                | TK::ColonColon
+                | TK::EqualEqual
+                | TK::PlusPlus
+                | TK::PlusEqual
+                | TK::MinusMinus
+                | TK::MinusEqual
+                | TK::Arrow
+                | TK::ArrowStar
+                | TK::StarEqual
+                | TK::SlashEqual
+                | TK::PercentEqual
+                | TK::ExclamationEqual
+                | TK::CaretEqual
+                | TK::CaretCaret
+                | TK::GreaterThanEqual
+                | TK::GreaterThanGreaterThan
+                | TK::GreaterThanGreaterThanEqual
+                | TK::LessThanEqual
+                | TK::LessThanLessThan
+                | TK::LessThanLessThanEqual
+                | TK::Spaceship
         )
     }
 
@@ -148,6 +246,77 @@ impl TokenKind {
         debug_assert!(self.is_multi_char() || !result);
         result
     }
+
+    /// The canonical spelling of tokens which are always spelled the same,
+    /// which are punctuators and a stray `\`.
+    #[must_use]
+    pub const fn spelling(self) -> Option<&'static str> {
+        match self {
+            TK::StrayBackSlash => Some("\\"),
+            _ => self.punctuator_spelling(),
+        }
+    }
+
+    /// The primary spelling of a punctuator, i.e. `[` for both `[` and `<:`.
+    #[must_use]
+    pub const fn punctuator_spelling(self) -> Option<&'static str> {
+        Some(match self {
+            TK::PoundPound => "##",
+            TK::AmpersandAmpersand => "&&",
+            TK::AmpersandEqual => "&=",
+            TK::PipePipe => "||",
+            TK::PipeEqual => "|=",
+            TK::DotStar => ".*",
+            TK::Ellipsis => "...",
+            TK::ColonColon => "::",
+            TK::EqualEqual => "==",
+            TK::PlusPlus => "++",
+            TK::PlusEqual => "+=",
+            TK::MinusMinus => "--",
+            TK::MinusEqual => "-=",
+            TK::Arrow => "->",
+            TK::ArrowStar => "->*",
+            TK::StarEqual => "*=",
+            TK::SlashEqual => "/=",
+            TK::PercentEqual => "%=",
+            TK::ExclamationEqual => "!=",
+            TK::CaretEqual => "^=",
+            TK::CaretCaret => "^^",
+            TK::GreaterThanEqual => ">=",
+            TK::GreaterThanGreaterThan => ">>",
+            TK::GreaterThanGreaterThanEqual => ">>=",
+            TK::LessThanEqual => "<=",
+            TK::LessThanLessThan => "<<",
+            TK::LessThanLessThanEqual => "<<=",
+            TK::Spaceship => "<=>",
+            TK::SemiColon => ";",
+            TK::Pound => "#",
+            TK::Ampersand => "&",
+            TK::Pipe => "|",
+            TK::Dot => ".",
+            TK::Comma => ",",
+            TK::QuestionMark => "?",
+            TK::Colon => ":",
+            TK::Equal => "=",
+            TK::Plus => "+",
+            TK::Minus => "-",
+            TK::Star => "*",
+            TK::Slash => "/",
+            TK::Percent => "%",
+            TK::Exclamation => "!",
+            TK::Tilde => "~",
+            TK::Caret => "^",
+            TK::GreaterThan => ">",
+            TK::LessThan => "<",
+            TK::OpenParen => "(",
+            TK::CloseParen => ")",
+            TK::OpenBrace => "{",
+            TK::CloseBrace => "}",
+            TK::OpenBracket => "[",
+            TK::CloseBracket => "]",
+            _ => return None,
+        })
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -165,11 +334,129 @@ impl NumberBase {
             NB::Binary => matches!(c, '0' | '1'),
             NB::Octal => matches!(c, '0'..='7'),
             NB::Decimal => c.is_ascii_digit(),
-            NB::Hexidecimal => matches!(c, '0'..='9' | 'a'..='f' | 'A'..='F'),
+            NB::Hexidecimal => c.is_ascii_hexdigit(),
         }
     }
 }
 
+/// Packed description of a preprocessing number so that later stages can
+/// classify the literal without re-scanning it.
+///
+/// A pp-number is split into the numeric body (prefix, digits, period and
+/// exponent) followed by a suffix which is everything the body does not cover,
+/// i.e. `ull` in `10ull` or `_km` in `123_km`.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct NumberInfo {
+    packed: u8,
+    suffix_len: u8,
+}
+
+impl NumberInfo {
+    const BASE_MASK: u8 = 0b11;
+    const HAS_SEP: u8 = 1 << 2;
+    const IS_FLOAT: u8 = 1 << 3;
+    const HAS_EXPONENT: u8 = 1 << 4;
+    const HAS_LONG_SUFFIX: u8 = 1 << 5;
+
+    #[must_use]
+    pub(crate) const fn new(base: NumberBase) -> Self {
+        let packed = match base {
+            NumberBase::Binary => 0,
+            NumberBase::Octal => 1,
+            NumberBase::Decimal => 2,
+            NumberBase::Hexidecimal => 3,
+        };
+        let suffix_len = 0;
+        Self { packed, suffix_len }
+    }
+
+    pub(crate) fn set_base(&mut self, base: NumberBase) {
+        self.packed = (self.packed & !Self::BASE_MASK) | Self::new(base).packed;
+    }
+
+    pub(crate) fn set_sep(&mut self) {
+        self.packed |= Self::HAS_SEP;
+    }
+
+    pub(crate) fn set_float(&mut self) {
+        self.packed |= Self::IS_FLOAT;
+    }
+
+    pub(crate) fn set_exponent(&mut self) {
+        self.packed |= Self::HAS_EXPONENT | Self::IS_FLOAT;
+    }
+
+    pub(crate) fn set_suffix_len(&mut self, len: u32) {
+        self.suffix_len = len.try_into().unwrap_or_else(|_| {
+            self.packed |= Self::HAS_LONG_SUFFIX;
+            u8::MAX
+        });
+    }
+
+    #[must_use]
+    pub const fn base(self) -> NumberBase {
+        match self.packed & Self::BASE_MASK {
+            0 => NumberBase::Binary,
+            1 => NumberBase::Octal,
+            2 => NumberBase::Decimal,
+            _ => NumberBase::Hexidecimal,
+        }
+    }
+
+    /// Indicates if a digit separator `'` occurs between digits of the body.
+    #[must_use]
+    pub const fn has_sep(self) -> bool {
+        self.packed & Self::HAS_SEP != 0
+    }
+
+    /// Indicates if the body contains a period or an exponent.
+    #[must_use]
+    pub const fn is_float(self) -> bool {
+        self.packed & Self::IS_FLOAT != 0
+    }
+
+    /// Indicates if the body contains an exponent with digits, i.e. `e-3` or
+    /// `p3`.
+    #[must_use]
+    pub const fn has_exponent(self) -> bool {
+        self.packed & Self::HAS_EXPONENT != 0
+    }
+
+    /// Length in bytes of the suffix at the end of the token, so the suffix
+    /// starts at `token.length() - suffix_len`. Saturates at [`u8::MAX`] when
+    /// [`Self::has_long_suffix`], in which case it is not the length.
+    #[must_use]
+    pub const fn suffix_len(self) -> u8 {
+        self.suffix_len
+    }
+
+    /// Indicates the suffix is longer than [`u8::MAX`] bytes and did not fit
+    /// in [`Self::suffix_len`]. Callers must then re-scan the token to find
+    /// where the suffix starts.
+    #[must_use]
+    pub const fn has_long_suffix(self) -> bool {
+        self.packed & Self::HAS_LONG_SUFFIX != 0
+    }
+
+    #[must_use]
+    pub const fn has_suffix(self) -> bool {
+        self.suffix_len != 0
+    }
+}
+
+impl core::fmt::Debug for NumberInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NumberInfo")
+            .field("base", &self.base())
+            .field("has_sep", &self.has_sep())
+            .field("is_float", &self.is_float())
+            .field("has_exponent", &self.has_exponent())
+            .field("suffix_len", &self.suffix_len())
+            .field("has_long_suffix", &self.has_long_suffix())
+            .finish()
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum LitType {
     /// String or char literal with no prefix
@@ -180,6 +467,21 @@ pub enum LitType {
     Utf32,
 }
 
+impl LitType {
+    /// Width of a code unit of the literal's encoding. Wide literals assume a
+    /// 32-bit `wchar_t`, as on most Unix-like targets, while
+    /// [`TargetInfo::wchar_width`](clowncc_version::TargetInfo::wchar_width)
+    /// gives the width for a specific target.
+    #[must_use]
+    pub const fn code_unit_bits(self) -> u32 {
+        match self {
+            LitType::Default | LitType::Utf8 => 8,
+            LitType::Utf16 => 16,
+            LitType::Wide | LitType::Utf32 => 32,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct DCharSeq {
     d_char: u8,
@@ -213,7 +515,8 @@ impl DCharSeq {
     }
 }
 
-// TODO: make an actual error type
+/// Why a raw string is malformed, reported in detail through the matching
+/// [`DiagCode`](crate::DiagCode).
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum RawStrErr {
     NotDChar,
@@ -246,9 +549,20 @@ impl Token {
             length,
             flags,
         };
-        debug_assert!(!kind.is_single_char() || length == 1);
-        debug_assert!(kind.is_multi_char() || !flags.has_new_line());
+        // Any token may hold line splices, the only newlines of a punctuator
+        debug_assert!(
+            !kind.is_single_char()
+                || length == 1
+                || flags.is_alt_spelling()
+                || flags.has_new_line()
+        );
         debug_assert!(kind.is_delimited() || !flags.is_unterminated());
+        debug_assert!(
+            matches!(
+                kind,
+                TK::Str { .. } | TK::CharSeq { .. } | TK::RawStr { .. }
+            ) || !flags.has_ud_suffix()
+        );
         token
     }
 
@@ -267,6 +581,32 @@ impl Token {
     pub const fn flags(&self) -> TokenFlags {
         self.flags
     }
+
+    /// Indicates a valid preprocessing-token, which is a
+    /// [pp-token kind](TokenKind::is_pp_token) that is terminated.
+    #[must_use]
+    pub const fn is_pp_token(&self) -> bool {
+        self.kind.is_pp_token() && !self.flags.is_unterminated()
+    }
+
+    /// Byte offset of the ud-suffix within the token's source text `text`, if
+    /// the literal has one. The literal without the suffix is
+    /// `&text[..offset]` and the suffix is `&text[offset..]`.
+    ///
+    /// Suffixes of [`TokenKind::Number`] are described by [`NumberInfo`].
+    #[must_use]
+    pub fn ud_suffix_offset(&self, text: &str) -> Option<usize> {
+        debug_assert!(text.len() == self.length as usize);
+        if !self.flags.has_ud_suffix() {
+            return None;
+        }
+        // The ud-suffix is an identifier so cannot contain the closing quote
+        let quote = match self.kind {
+            TK::CharSeq { .. } => '\'',
+            _ => '"',
+        };
+        text.rfind(quote).map(|idx| idx + 1)
+    }
 }
 
 bitflags::bitflags! {
@@ -279,6 +619,12 @@ bitflags::bitflags! {
         const NEWLINE = (1 << 0);
         /// Indicates if delimited token has a closing delimiter.
         const UNTERMINATED = (1 << 1);
+        /// Indicates the punctuator was spelled as a digraph, i.e. `<:`.
+        const DIGRAPH = (1 << 2);
+        /// Indicates a trigraph was replaced within the token, i.e. `??=`.
+        const TRIGRAPH = (1 << 3);
+        /// Indicates a literal is followed by a C++11 ud-suffix, i.e. `"s"_x`.
+        const UD_SUFFIX = (1 << 4);
     }
 }
 
@@ -295,4 +641,30 @@ impl TokenFlags {
     pub const fn is_unterminated(self) -> bool {
         self.contains(Self::UNTERMINATED)
     }
+
+    /// Indicates if the punctuator is spelled with a digraph rather than its
+    /// primary spelling.
+    #[must_use]
+    pub const fn is_digraph(self) -> bool {
+        self.contains(Self::DIGRAPH)
+    }
+
+    /// Indicates if token contains a trigraph that was replaced.
+    #[must_use]
+    pub const fn has_trigraph(self) -> bool {
+        self.contains(Self::TRIGRAPH)
+    }
+
+    /// Indicates if a string or character literal has a ud-suffix.
+    #[must_use]
+    pub const fn has_ud_suffix(self) -> bool {
+        self.contains(Self::UD_SUFFIX)
+    }
+
+    /// Indicates if the token is not spelled with its primary spelling, so its
+    /// length may differ from the length of the primary spelling.
+    #[must_use]
+    pub const fn is_alt_spelling(self) -> bool {
+        self.intersects(Self::DIGRAPH.union(Self::TRIGRAPH))
+    }
 }
@@ -0,0 +1,236 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{Cursor, Span, Token, TokenFlags, TokenKind};
+
+/// Replacement of the text in a range of the source by new text, described
+/// only by the length of the new text.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TextEdit {
+    old: Span,
+    new_len: u32,
+}
+
+impl TextEdit {
+    #[must_use]
+    pub const fn new(old: Span, new_len: u32) -> TextEdit {
+        TextEdit { old, new_len }
+    }
+
+    /// The range of the source before the edit which was replaced.
+    #[must_use]
+    pub const fn old(self) -> Span {
+        self.old
+    }
+
+    /// The range of the source after the edit holding the new text.
+    #[must_use]
+    pub const fn new_span(self) -> Span {
+        Span::new(self.old.start(), self.old.start() + self.new_len)
+    }
+}
+
+/// Every token of a source stored as parallel arrays of kinds, start offsets,
+/// lengths and flags, so passes which only look at kinds stay within a dense
+/// slice.
+///
+/// The buffer is meant to be reused. Tokenizing clears it but keeps its
+/// allocations, so once it has grown to fit a source, tokenizing a source with
+/// as many tokens does not allocate.
+#[derive(Clone, Debug, Default)]
+pub struct TokenBuffer {
+    kinds: Vec<TokenKind>,
+    starts: Vec<u32>,
+    lengths: Vec<u32>,
+    flags: Vec<TokenFlags>,
+}
+
+impl TokenBuffer {
+    #[must_use]
+    pub const fn new() -> TokenBuffer {
+        TokenBuffer {
+            kinds: Vec::new(),
+            starts: Vec::new(),
+            lengths: Vec::new(),
+            flags: Vec::new(),
+        }
+    }
+
+    /// Replaces the contents of the buffer with all remaining tokens of the
+    /// cursor. Offsets are relative to where the cursor was when passed in.
+    ///
+    /// Header names are never lexed since that depends on the surrounding
+    /// tokens.
+    pub fn tokenize(&mut self, mut cursor: Cursor<'_>) {
+        self.clear();
+        let mut offset = 0;
+        while let Some(token) = cursor.next_token() {
+            self.kinds.push(token.kind());
+            self.starts.push(offset);
+            self.lengths.push(token.length());
+            self.flags.push(token.flags());
+            offset += token.length();
+        }
+    }
+
+    /// Updates the buffer after `edit` was applied to the source, lexing only
+    /// the tokens near the edit. `cursor` must start at the beginning of the
+    /// edited source and be configured like the cursor which filled the
+    /// buffer.
+    ///
+    /// Returns the indices of the tokens which were lexed again. Tokens before
+    /// the range are unchanged and tokens after it only moved by the change in
+    /// length.
+    pub fn relex(
+        &mut self,
+        mut cursor: Cursor<'_>,
+        edit: TextEdit,
+    ) -> Range<usize> {
+        let code = cursor.as_str();
+        let old = edit.old();
+        debug_assert!(
+            code.len() + old.len() as usize
+                == self.source_len() as usize + edit.new_len as usize
+        );
+
+        // Tokens never look past a newline unless it is part of a line splice,
+        // so the token holding the newline before the edited logical line is
+        // the first one which may lex differently.
+        let trigraphs = cursor.lang_opts().has_trigraphs();
+        let mut line_start = &code[..old.start() as usize];
+        let first = loop {
+            let Some(newline) = line_start.rfind('\n') else {
+                break 0;
+            };
+            line_start = &line_start[..newline];
+            if !ends_with_splice(line_start, trigraphs) {
+                break self.token_at(newline as u32);
+            }
+        };
+        let restart = self.starts.get(first).copied().unwrap_or_default();
+        cursor.skip_bytes(restart);
+
+        // Lexing is stateless, so once a new token starts where an old token
+        // after the edit started, all following tokens are the same.
+        let shift = |start: u32| start - old.end() + edit.new_span().end();
+        let mut resync = self.starts.partition_point(|&s| s < old.end());
+        let mut tokens = Vec::new();
+        let mut offset = restart;
+        let resync = loop {
+            let Some(token) = cursor.next_token() else {
+                break self.len();
+            };
+            offset += token.length();
+            tokens.push(token);
+            while resync < self.len() && shift(self.starts[resync]) < offset {
+                resync += 1;
+            }
+            if resync < self.len() && shift(self.starts[resync]) == offset {
+                break resync;
+            }
+        };
+
+        let mut start = restart;
+        let starts = tokens.iter().map(|token| {
+            let token_start = start;
+            start += token.length();
+            token_start
+        });
+        let starts: Vec<_> = starts.collect();
+        self.kinds
+            .splice(first..resync, tokens.iter().map(Token::kind));
+        self.lengths
+            .splice(first..resync, tokens.iter().map(Token::length));
+        self.flags
+            .splice(first..resync, tokens.iter().map(Token::flags));
+        self.starts.splice(first..resync, starts);
+        let changed = first..first + tokens.len();
+        for token_start in &mut self.starts[changed.end..] {
+            *token_start = shift(*token_start);
+        }
+        changed
+    }
+
+    /// Index of the token holding the byte at `offset`.
+    fn token_at(&self, offset: u32) -> usize {
+        self.starts.partition_point(|&start| start <= offset) - 1
+    }
+
+    /// Length of the tokenized source.
+    fn source_len(&self) -> u32 {
+        match (self.starts.last(), self.lengths.last()) {
+            (Some(start), Some(length)) => start + length,
+            _ => 0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.kinds.clear();
+        self.starts.clear();
+        self.lengths.clear();
+        self.flags.clear();
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    #[must_use]
+    pub fn kinds(&self) -> &[TokenKind] {
+        &self.kinds
+    }
+
+    /// Byte offset of the first character of each token.
+    #[must_use]
+    pub fn starts(&self) -> &[u32] {
+        &self.starts
+    }
+
+    #[must_use]
+    pub fn lengths(&self) -> &[u32] {
+        &self.lengths
+    }
+
+    #[must_use]
+    pub fn flags(&self) -> &[TokenFlags] {
+        &self.flags
+    }
+
+    /// Reassembles the token at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn token(&self, index: usize) -> Token {
+        Token::new(self.kinds[index], self.lengths[index], self.flags[index])
+    }
+
+    /// The source range of the token at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn span(&self, index: usize) -> Span {
+        let start = self.starts[index];
+        Span::new(start, start + self.lengths[index])
+    }
+
+    /// Reassembles every token in order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Token> + '_ {
+        (0..self.len()).map(|index| self.token(index))
+    }
+}
+
+/// Whether the line before a newline ends in the backslash of a line splice.
+fn ends_with_splice(line: &str, trigraphs: bool) -> bool {
+    let line = line.trim_end_matches(|c: char| c != '\n' && c.is_whitespace());
+    line.ends_with('\\') || (trigraphs && line.ends_with("??/"))
+}
@@ -0,0 +1,83 @@
+use clowncc_token::{collect_comments, Cursor};
+
+use clowncc_version::StdVersion;
+use expect_test::{expect, Expect};
+
+use std::fmt::Write;
+
+fn check_comments(code: &str, expect: Expect) {
+    let mut out = String::new();
+    for comment in collect_comments(Cursor::new(code, StdVersion::Cpp26)) {
+        let documents = comment
+            .documents()
+            .map(|span| &code[span.start() as usize..span.end() as usize]);
+        writeln!(out, "{:?} {documents:?}", comment.style()).unwrap();
+        for line in comment.contents().lines() {
+            writeln!(out, "  |{line}").unwrap();
+        }
+    }
+    expect.assert_eq(&out);
+}
+
+#[test]
+fn line_doc_comments() {
+    check_comments(
+        "/// Adds one.\n///\n///   x + 1\nint inc(int x);\n\
+         //! Also documents the next token\n\
+         //// Decoration\n// Plain\n\
+         int a; ///< The a.\n\
+         int b; //!< The b \\\n  continued.\n",
+        expect![[r#"
+            DocNext Some("int")
+              |Adds one.
+            DocNext Some("int")
+            DocNext Some("int")
+              |  x + 1
+            DocNext Some("int")
+              |Also documents the next token
+            Plain None
+              |// Decoration
+            Plain None
+              |Plain
+            DocPrevious Some("a")
+              |The a.
+            DocPrevious Some("b")
+              |The b   continued.
+        "#]],
+    );
+}
+
+#[test]
+fn block_doc_comments() {
+    check_comments(
+        "/**\n * Frobs.\n *\n * @param x  value\n */\nvoid frob(int x);\n\
+         /*! Qt style\n    keeps\n      relative indent */ struct S;\n\
+         enum { A /**< First */, B /*!< Second */ };\n\
+         /***********/ /**/ /* plain */\n\
+         #include <h.h> /**< The header */\n\
+         /** Dangling",
+        expect![[r#"
+            DocNext Some("void")
+              |Frobs.
+              |
+              |@param x  value
+            DocNext Some("struct")
+              |Qt style
+              |keeps
+              |  relative indent
+            DocPrevious Some("A")
+              |First
+            DocPrevious Some("B")
+              |Second
+            Plain None
+              |********
+            Plain None
+            Plain None
+              |plain
+            DocPrevious Some("<h.h>")
+              |The header
+            DocNext None
+              |Dangling
+        "#]],
+    );
+}
@@ -0,0 +1,702 @@
+//! Table driven checks of the lexical grammar of C17, C23 and C++23 which are
+//! run through [`Cursor`] for every [`StdVersion`] a case applies to.
+
+use clowncc_token::{Cursor, LitType, NumberBase, Token, TokenKind as TK};
+
+use clowncc_version::{StdVersion, StdVersion::*};
+
+const ALL_VERSIONS: [StdVersion; 14] = [
+    C89, C95, C99, C11, C17, C23, C2y, Cpp98, Cpp11, Cpp14, Cpp17, Cpp20,
+    Cpp23, Cpp26,
+];
+
+/// Expected kind of a token, loose for kinds whose payload the tests cannot
+/// construct.
+#[derive(Copy, Clone, Debug)]
+enum Kind {
+    Is(TK),
+    Num(NumberBase),
+    RawStr(LitType),
+}
+
+impl Kind {
+    fn matches(self, kind: TK) -> bool {
+        match (self, kind) {
+            (Kind::Is(expected), kind) => expected == kind,
+            (Kind::Num(base), TK::Number(info)) => info.base() == base,
+            (Kind::RawStr(expected), TK::RawStr { lit_type, .. }) => {
+                lit_type == expected
+            }
+            (_, _) => false,
+        }
+    }
+}
+
+const ID: Kind = Kind::Is(TK::Identifier {
+    has_univ_char: false,
+});
+const UNIV_ID: Kind = Kind::Is(TK::Identifier {
+    has_univ_char: true,
+});
+const WS: Kind = Kind::Is(TK::Whitespace {
+    splits_lines: false,
+});
+const NL: Kind = Kind::Is(TK::Whitespace { splits_lines: true });
+const STR: Kind = Kind::Is(TK::Str {
+    lit_type: LitType::Default,
+    has_esc: false,
+});
+const ESC_STR: Kind = Kind::Is(TK::Str {
+    lit_type: LitType::Default,
+    has_esc: true,
+});
+const CHAR: Kind = Kind::Is(TK::CharSeq {
+    lit_type: LitType::Default,
+    has_esc: false,
+});
+const ESC_CHAR: Kind = Kind::Is(TK::CharSeq {
+    lit_type: LitType::Default,
+    has_esc: true,
+});
+const RAW_STR: Kind = Kind::RawStr(LitType::Default);
+const DEC: Kind = Kind::Num(NumberBase::Decimal);
+const OCT: Kind = Kind::Num(NumberBase::Octal);
+const HEX: Kind = Kind::Num(NumberBase::Hexidecimal);
+const BIN: Kind = Kind::Num(NumberBase::Binary);
+
+/// Whether a case or punctuator applies to a standard version.
+type Applies = fn(StdVersion) -> bool;
+
+fn all(_: StdVersion) -> bool {
+    true
+}
+
+fn c(sv: StdVersion) -> bool {
+    sv.is_c()
+}
+
+fn cpp(sv: StdVersion) -> bool {
+    sv.is_cpp()
+}
+
+const fn str_of(lit_type: LitType) -> Kind {
+    Kind::Is(TK::Str {
+        lit_type,
+        has_esc: false,
+    })
+}
+
+const fn char_of(lit_type: LitType) -> Kind {
+    Kind::Is(TK::CharSeq {
+        lit_type,
+        has_esc: false,
+    })
+}
+
+fn has_utf_prefixes(sv: StdVersion) -> bool {
+    sv.is_since_c11() || sv.is_since_cpp11()
+}
+
+fn has_u8_char(sv: StdVersion) -> bool {
+    sv.is_since_c23() || sv.is_since_cpp17()
+}
+
+fn has_raw_str(sv: StdVersion) -> bool {
+    sv.is_since_cpp11()
+}
+
+fn has_ud_suffix(sv: StdVersion) -> bool {
+    sv.is_since_cpp11()
+}
+
+fn has_digraphs(sv: StdVersion) -> bool {
+    sv.is_cpp() || sv.is_since_c95()
+}
+
+fn has_line_comments(sv: StdVersion) -> bool {
+    sv.is_cpp() || sv.is_since_c99()
+}
+
+fn has_scope(sv: StdVersion) -> bool {
+    sv.is_cpp() || sv.is_since_c23()
+}
+
+fn has_digit_sep(sv: StdVersion) -> bool {
+    sv.is_since_c23() || sv.is_since_cpp14()
+}
+
+fn has_hex_float(sv: StdVersion) -> bool {
+    sv.is_since_c99() || sv.is_since_cpp17()
+}
+
+/// Source which lexes into the given tokens for the versions it applies to.
+struct Case {
+    code: &'static str,
+    applies: Applies,
+    tokens: &'static [(Kind, u32)],
+}
+
+const fn case(
+    code: &'static str,
+    applies: Applies,
+    tokens: &'static [(Kind, u32)],
+) -> Case {
+    Case {
+        code,
+        applies,
+        tokens,
+    }
+}
+
+fn next_token(cursor: &mut Cursor<'_>) -> Option<Token> {
+    cursor.next_token()
+}
+
+fn next_token_header(cursor: &mut Cursor<'_>) -> Option<Token> {
+    cursor.next_token_header()
+}
+
+fn check_cases(cases: &[Case], tok_fn: fn(&mut Cursor<'_>) -> Option<Token>) {
+    for case in cases {
+        for sv in ALL_VERSIONS.into_iter().filter(|&sv| (case.applies)(sv)) {
+            let mut cursor = Cursor::new(case.code, sv);
+            let tokens: Vec<_> = std::iter::from_fn(|| tok_fn(&mut cursor))
+                .map(|t| (t.kind(), t.length()))
+                .collect();
+            let matches = tokens.len() == case.tokens.len()
+                && tokens.iter().zip(case.tokens).all(
+                    |(&(kind, len), &(expected, expected_len))| {
+                        expected.matches(kind) && len == expected_len
+                    },
+                );
+            assert!(
+                matches,
+                "{:?} for {}:\n  expected: {:?}\n    actual: {:?}",
+                case.code,
+                sv.as_str(),
+                case.tokens,
+                tokens,
+            );
+        }
+    }
+}
+
+/// Every punctuator of the three grammars along with the versions which
+/// recognize it as a single token.
+const PUNCTUATORS: &[(&str, TK, Applies)] = &[
+    ("[", TK::OpenBracket, all),
+    ("]", TK::CloseBracket, all),
+    ("(", TK::OpenParen, all),
+    (")", TK::CloseParen, all),
+    ("{", TK::OpenBrace, all),
+    ("}", TK::CloseBrace, all),
+    (".", TK::Dot, all),
+    ("->", TK::Arrow, all),
+    ("++", TK::PlusPlus, all),
+    ("--", TK::MinusMinus, all),
+    ("&", TK::Ampersand, all),
+    ("*", TK::Star, all),
+    ("+", TK::Plus, all),
+    ("-", TK::Minus, all),
+    ("~", TK::Tilde, all),
+    ("!", TK::Exclamation, all),
+    ("/", TK::Slash, all),
+    ("%", TK::Percent, all),
+    ("<<", TK::LessThanLessThan, all),
+    (">>", TK::GreaterThanGreaterThan, all),
+    ("<", TK::LessThan, all),
+    (">", TK::GreaterThan, all),
+    ("<=", TK::LessThanEqual, all),
+    (">=", TK::GreaterThanEqual, all),
+    ("==", TK::EqualEqual, all),
+    ("!=", TK::ExclamationEqual, all),
+    ("^", TK::Caret, all),
+    ("|", TK::Pipe, all),
+    ("&&", TK::AmpersandAmpersand, all),
+    ("||", TK::PipePipe, all),
+    ("?", TK::QuestionMark, all),
+    (":", TK::Colon, all),
+    (";", TK::SemiColon, all),
+    ("...", TK::Ellipsis, all),
+    ("=", TK::Equal, all),
+    ("*=", TK::StarEqual, all),
+    ("/=", TK::SlashEqual, all),
+    ("%=", TK::PercentEqual, all),
+    ("+=", TK::PlusEqual, all),
+    ("-=", TK::MinusEqual, all),
+    ("<<=", TK::LessThanLessThanEqual, all),
+    (">>=", TK::GreaterThanGreaterThanEqual, all),
+    ("&=", TK::AmpersandEqual, all),
+    ("^=", TK::CaretEqual, all),
+    ("|=", TK::PipeEqual, all),
+    (",", TK::Comma, all),
+    ("#", TK::Pound, all),
+    ("##", TK::PoundPound, all),
+    ("<:", TK::OpenBracket, has_digraphs),
+    (":>", TK::CloseBracket, has_digraphs),
+    ("<%", TK::OpenBrace, has_digraphs),
+    ("%>", TK::CloseBrace, has_digraphs),
+    ("%:", TK::Pound, has_digraphs),
+    ("%:%:", TK::PoundPound, has_digraphs),
+    ("::", TK::ColonColon, has_scope),
+    (".*", TK::DotStar, cpp),
+    ("->*", TK::ArrowStar, cpp),
+    ("<=>", TK::Spaceship, |sv| sv.is_since_cpp20()),
+    ("^^", TK::CaretCaret, |sv| sv.is_since_cpp26()),
+];
+
+#[test]
+fn punctuators() {
+    for &(spelling, kind, applies) in PUNCTUATORS {
+        for sv in ALL_VERSIONS.into_iter().filter(|&sv| applies(sv)) {
+            let mut cursor = Cursor::new(spelling, sv);
+            let token = cursor.next_token().unwrap();
+            assert_eq!(
+                (token.kind(), token.length() as usize),
+                (kind, spelling.len()),
+                "{spelling:?} for {}",
+                sv.as_str(),
+            );
+            assert!(cursor.next_token().is_none());
+        }
+    }
+}
+
+#[test]
+fn punctuators_in_older_versions() {
+    check_cases(
+        &[
+            case(
+                "<:%>",
+                |sv| !has_digraphs(sv),
+                &[
+                    (Kind::Is(TK::LessThan), 1),
+                    (Kind::Is(TK::Colon), 1),
+                    (Kind::Is(TK::Percent), 1),
+                    (Kind::Is(TK::GreaterThan), 1),
+                ],
+            ),
+            case(
+                "::",
+                |sv| !has_scope(sv),
+                &[(Kind::Is(TK::Colon), 1), (Kind::Is(TK::Colon), 1)],
+            ),
+            case(
+                ".*->*",
+                c,
+                &[
+                    (Kind::Is(TK::Dot), 1),
+                    (Kind::Is(TK::Star), 1),
+                    (Kind::Is(TK::Arrow), 2),
+                    (Kind::Is(TK::Star), 1),
+                ],
+            ),
+            case(
+                "<=>",
+                |sv| !sv.is_since_cpp20(),
+                &[
+                    (Kind::Is(TK::LessThanEqual), 2),
+                    (Kind::Is(TK::GreaterThan), 1),
+                ],
+            ),
+            case(
+                "^^",
+                |sv| !sv.is_since_cpp26(),
+                &[(Kind::Is(TK::Caret), 1), (Kind::Is(TK::Caret), 1)],
+            ),
+        ],
+        next_token,
+    );
+}
+
+#[test]
+fn maximal_munch() {
+    check_cases(
+        &[
+            case(
+                "a+++++b",
+                all,
+                &[
+                    (ID, 1),
+                    (Kind::Is(TK::PlusPlus), 2),
+                    (Kind::Is(TK::PlusPlus), 2),
+                    (Kind::Is(TK::Plus), 1),
+                    (ID, 1),
+                ],
+            ),
+            case(
+                "x<<=y>>=z",
+                all,
+                &[
+                    (ID, 1),
+                    (Kind::Is(TK::LessThanLessThanEqual), 3),
+                    (ID, 1),
+                    (Kind::Is(TK::GreaterThanGreaterThanEqual), 3),
+                    (ID, 1),
+                ],
+            ),
+            case(
+                "....",
+                all,
+                &[(Kind::Is(TK::Ellipsis), 3), (Kind::Is(TK::Dot), 1)],
+            ),
+            case("..", all, &[(Kind::Is(TK::Dot), 1), (Kind::Is(TK::Dot), 1)]),
+            case(
+                "x<::y",
+                has_raw_str,
+                &[
+                    (ID, 1),
+                    (Kind::Is(TK::LessThan), 1),
+                    (Kind::Is(TK::ColonColon), 2),
+                    (ID, 1),
+                ],
+            ),
+            case(
+                "x<::>",
+                cpp,
+                &[
+                    (ID, 1),
+                    (Kind::Is(TK::OpenBracket), 2),
+                    (Kind::Is(TK::CloseBracket), 2),
+                ],
+            ),
+            case(
+                "%:%",
+                has_digraphs,
+                &[(Kind::Is(TK::Pound), 2), (Kind::Is(TK::Percent), 1)],
+            ),
+        ],
+        next_token,
+    );
+}
+
+#[test]
+fn identifiers() {
+    check_cases(
+        &[
+            case("_abc123", all, &[(ID, 7)]),
+            case("\u{e9}t\u{e9}", all, &[(ID, 5)]),
+            case("a\\u00e9", all, &[(UNIV_ID, 7)]),
+            case("\\U000000e9b", all, &[(UNIV_ID, 11)]),
+            case(
+                "a\\u00e",
+                all,
+                &[(ID, 1), (Kind::Is(TK::StrayBackSlash), 1), (ID, 4)],
+            ),
+            case(
+                "L u U R u8",
+                all,
+                &[
+                    (ID, 1),
+                    (WS, 1),
+                    (ID, 1),
+                    (WS, 1),
+                    (ID, 1),
+                    (WS, 1),
+                    (ID, 1),
+                    (WS, 1),
+                    (ID, 2),
+                ],
+            ),
+        ],
+        next_token,
+    );
+}
+
+#[test]
+fn pp_numbers() {
+    check_cases(
+        &[
+            case("0", all, &[(DEC, 1)]),
+            case("123", all, &[(DEC, 3)]),
+            case("0123", all, &[(OCT, 4)]),
+            case("0x1F", all, &[(HEX, 4)]),
+            case("0b101", all, &[(BIN, 5)]),
+            case("1.5e+3", all, &[(DEC, 6)]),
+            case(".5", all, &[(DEC, 2)]),
+            case("1.", all, &[(DEC, 2)]),
+            case("1..2", all, &[(DEC, 4)]),
+            case("1e+", all, &[(DEC, 3)]),
+            case("0x1e+1", all, &[(HEX, 6)]),
+            case("1ull", all, &[(DEC, 4)]),
+            case("1.0f", all, &[(DEC, 4)]),
+            case("1_km", all, &[(DEC, 4)]),
+            case("0x1.8p-2", has_hex_float, &[(HEX, 8)]),
+            case(
+                "0x1.8p-2",
+                |sv| !has_hex_float(sv),
+                &[(HEX, 6), (Kind::Is(TK::Minus), 1), (DEC, 1)],
+            ),
+            case("1'000", has_digit_sep, &[(DEC, 5)]),
+            case(
+                "1'000",
+                |sv| !has_digit_sep(sv),
+                &[
+                    (DEC, 1),
+                    (
+                        Kind::Is(TK::CharSeq {
+                            lit_type: LitType::Default,
+                            has_esc: false,
+                        }),
+                        4,
+                    ),
+                ],
+            ),
+            case(
+                "0x",
+                all,
+                &[(
+                    Kind::Is(TK::StrayNumPrefix {
+                        base: NumberBase::Hexidecimal,
+                    }),
+                    2,
+                )],
+            ),
+        ],
+        next_token,
+    );
+}
+
+#[test]
+fn literals() {
+    check_cases(
+        &[
+            case("'a'", all, &[(CHAR, 3)]),
+            case("'ab'", all, &[(CHAR, 4)]),
+            case("'\\''", all, &[(ESC_CHAR, 4)]),
+            case("\"a\"", all, &[(STR, 3)]),
+            case("\"\"", all, &[(STR, 2)]),
+            case("\"\\\"\"", all, &[(ESC_STR, 4)]),
+            case("\"\\\\\" x", all, &[(ESC_STR, 4), (WS, 1), (ID, 1)]),
+            case("\"'\" '\"'", all, &[(STR, 3), (WS, 1), (CHAR, 3)]),
+            case("R\"(a)\"", has_raw_str, &[(RAW_STR, 6)]),
+            case("R\"x(a)\")x\"", has_raw_str, &[(RAW_STR, 10)]),
+            case("R\"(a)\"", |sv| !has_raw_str(sv), &[(ID, 1), (STR, 5)]),
+            case(
+                "\"a\"_x 'b'_y",
+                has_ud_suffix,
+                &[(STR, 5), (WS, 1), (CHAR, 5)],
+            ),
+            case("\"a\"_x", |sv| !has_ud_suffix(sv), &[(STR, 3), (ID, 2)]),
+        ],
+        next_token,
+    );
+}
+
+#[test]
+fn literal_prefixes() {
+    use LitType::{Utf16, Utf32, Utf8, Wide};
+    const WIDE_CHAR: Kind = char_of(Wide);
+    const WIDE_STR: Kind = str_of(Wide);
+    const U16_CHAR: Kind = char_of(Utf16);
+    const U32_STR: Kind = str_of(Utf32);
+    const U8_CHAR: Kind = char_of(Utf8);
+    const U8_STR: Kind = str_of(Utf8);
+
+    check_cases(
+        &[
+            case(
+                "L'a' L\"a\"",
+                all,
+                &[(WIDE_CHAR, 4), (WS, 1), (WIDE_STR, 4)],
+            ),
+            case(
+                "u'a' U\"a\" u8\"a\"",
+                has_utf_prefixes,
+                &[(U16_CHAR, 4), (WS, 1), (U32_STR, 4), (WS, 1), (U8_STR, 5)],
+            ),
+            case(
+                "u'a' U\"a\" u8\"a\"",
+                |sv| !has_utf_prefixes(sv),
+                &[
+                    (ID, 1),
+                    (CHAR, 3),
+                    (WS, 1),
+                    (ID, 1),
+                    (STR, 3),
+                    (WS, 1),
+                    (ID, 2),
+                    (STR, 3),
+                ],
+            ),
+            case("u8'a'", has_u8_char, &[(U8_CHAR, 5)]),
+            case("u8'a'", |sv| !has_u8_char(sv), &[(ID, 2), (CHAR, 3)]),
+            case(
+                "LR\"(a)\" uR\"(a)\" UR\"(a)\" u8R\"(a)\"",
+                has_raw_str,
+                &[
+                    (Kind::RawStr(Wide), 7),
+                    (WS, 1),
+                    (Kind::RawStr(Utf16), 7),
+                    (WS, 1),
+                    (Kind::RawStr(Utf32), 7),
+                    (WS, 1),
+                    (Kind::RawStr(Utf8), 8),
+                ],
+            ),
+            case(
+                "LR\"(a)\" u8R\"(a)\"",
+                |sv| !has_raw_str(sv),
+                &[(ID, 2), (STR, 5), (WS, 1), (ID, 3), (STR, 5)],
+            ),
+            case(
+                "L'a'_x u8\"a\"_y",
+                has_ud_suffix,
+                &[(WIDE_CHAR, 6), (WS, 1), (U8_STR, 7)],
+            ),
+        ],
+        next_token,
+    );
+}
+
+#[test]
+fn header_names() {
+    check_cases(
+        &[
+            case("<stdio.h>", all, &[(Kind::Is(TK::SystemHeader), 9)]),
+            case("\"a.h\"", all, &[(Kind::Is(TK::Header), 5)]),
+            case("<a\\b>", all, &[(Kind::Is(TK::SystemHeader), 5)]),
+        ],
+        next_token_header,
+    );
+}
+
+#[test]
+fn comments() {
+    check_cases(
+        &[
+            case("/* a */b", all, &[(Kind::Is(TK::BlockComment), 7), (ID, 1)]),
+            case(
+                "/**/*",
+                all,
+                &[(Kind::Is(TK::BlockComment), 4), (Kind::Is(TK::Star), 1)],
+            ),
+            case(
+                "// a\nb",
+                has_line_comments,
+                &[(Kind::Is(TK::LineComment), 4), (NL, 1), (ID, 1)],
+            ),
+            case(
+                "a //* c */ b",
+                |sv| !has_line_comments(sv),
+                &[
+                    (ID, 1),
+                    (WS, 1),
+                    (Kind::Is(TK::Slash), 1),
+                    (Kind::Is(TK::BlockComment), 7),
+                    (WS, 1),
+                    (ID, 1),
+                ],
+            ),
+        ],
+        next_token,
+    );
+}
+
+#[test]
+fn line_splices() {
+    const U8_CHAR: Kind = char_of(LitType::Utf8);
+    check_cases(
+        &[
+            case("ab\\\ncd", all, &[(ID, 6)]),
+            case("a\\\n", all, &[(ID, 3)]),
+            case("\\\na", all, &[(ID, 3)]),
+            case(";\\\n", all, &[(Kind::Is(TK::SemiColon), 1), (WS, 2)]),
+            case(
+                "; \\  \n+",
+                all,
+                &[
+                    (Kind::Is(TK::SemiColon), 1),
+                    (WS, 5),
+                    (Kind::Is(TK::Plus), 1),
+                ],
+            ),
+            case("12\\\n34", all, &[(DEC, 6)]),
+            case("1\\\n.5", all, &[(DEC, 5)]),
+            case("\"a\\\nb\"", all, &[(ESC_STR, 6)]),
+            case("\"\\\\\n\"\"", all, &[(ESC_STR, 6)]),
+            case("'\\\\\n''", all, &[(ESC_CHAR, 6)]),
+            case(
+                "// a\\\nb\nc",
+                has_line_comments,
+                &[(Kind::Is(TK::LineComment), 7), (NL, 1), (ID, 1)],
+            ),
+            case("\\u00\\\ne9", all, &[(UNIV_ID, 8)]),
+            case(
+                "\\ x",
+                all,
+                &[(Kind::Is(TK::StrayBackSlash), 1), (WS, 1), (ID, 1)],
+            ),
+            case(
+                "+\\\n\\x",
+                all,
+                &[
+                    (Kind::Is(TK::Plus), 1),
+                    (Kind::Is(TK::StrayBackSlash), 3),
+                    (ID, 1),
+                ],
+            ),
+            // Splices are removed before tokenization so they never split
+            // punctuators or pp-numbers
+            case(
+                "a-\\\n>b",
+                all,
+                &[(ID, 1), (Kind::Is(TK::Arrow), 4), (ID, 1)],
+            ),
+            case(
+                "a+\\\n+b",
+                all,
+                &[(ID, 1), (Kind::Is(TK::PlusPlus), 4), (ID, 1)],
+            ),
+            case("a+\\\nb", all, &[(ID, 1), (Kind::Is(TK::Plus), 1), (ID, 3)]),
+            case(
+                "/\\\n/ c",
+                has_line_comments,
+                &[(Kind::Is(TK::LineComment), 6)],
+            ),
+            case(
+                "/\\\n/ c",
+                |sv| !has_line_comments(sv),
+                &[
+                    (Kind::Is(TK::Slash), 1),
+                    (Kind::Is(TK::Slash), 3),
+                    (WS, 1),
+                    (ID, 1),
+                ],
+            ),
+            case("/\\\n* a *\\\n/", all, &[(Kind::Is(TK::BlockComment), 11)]),
+            case("<\\\n<=", all, &[(Kind::Is(TK::LessThanLessThanEqual), 5)]),
+            case(
+                "<=\\\n>",
+                |sv| sv.is_since_cpp20(),
+                &[(Kind::Is(TK::Spaceship), 5)],
+            ),
+            case(
+                "<=\\\n>",
+                |sv| !sv.is_since_cpp20(),
+                &[
+                    (Kind::Is(TK::LessThanEqual), 2),
+                    (Kind::Is(TK::GreaterThan), 3),
+                ],
+            ),
+            case("%:\\\n%:", has_digraphs, &[(Kind::Is(TK::PoundPound), 6)]),
+            case(
+                "%:\\\n%:",
+                |sv| !has_digraphs(sv),
+                &[
+                    (Kind::Is(TK::Percent), 1),
+                    (Kind::Is(TK::Colon), 1),
+                    (Kind::Is(TK::Percent), 3),
+                    (Kind::Is(TK::Colon), 1),
+                ],
+            ),
+            case("1e\\\n+5", all, &[(DEC, 6)]),
+            case("0\\\nx1\\\n.\\\n8", all, &[(HEX, 11)]),
+            case("R\\\n", all, &[(ID, 3)]),
+            case("uR\\\n:", all, &[(ID, 4), (Kind::Is(TK::Colon), 1)]),
+            case("u8\\\n'a'", has_u8_char, &[(U8_CHAR, 7)]),
+            case("u8\\\n'a'", |sv| !has_u8_char(sv), &[(ID, 4), (CHAR, 3)]),
+        ],
+        next_token,
+    );
+}
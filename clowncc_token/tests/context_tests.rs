@@ -0,0 +1,60 @@
+use clowncc_token::{ContextTokens, Cursor, TokenKind};
+
+use clowncc_version::StdVersion;
+
+/// Texts of the header-names lexed from `code`.
+fn headers(sv: StdVersion, code: &str) -> Vec<&str> {
+    let mut tokens = ContextTokens::new(Cursor::new(code, sv));
+    let headers = tokens
+        .by_ref()
+        .filter(|(token, _)| {
+            matches!(token.kind(), TokenKind::Header | TokenKind::SystemHeader)
+        })
+        .map(|(_, text)| text)
+        .collect();
+    assert_eq!(tokens.offset() as usize, code.len());
+    headers
+}
+
+#[test]
+fn include_directives() {
+    let code = "#include <a.h>\n  # /**/ include \"b.h\"\n%:include_next <c>\n\
+                #import <d>\n#include\\\n<e>\n#inc\\\nlude <f>";
+    assert_eq!(
+        headers(StdVersion::C17, code),
+        ["<a.h>", "\"b.h\"", "<c>", "<d>", "<e>", "<f>"]
+    );
+}
+
+#[test]
+fn not_header_names() {
+    let code = "a < b > c;\nx #include <a>\n#define include <b>\n\
+                #include <c> <d>\n#if 1 < 2\n#include\n<e>";
+    assert_eq!(headers(StdVersion::C17, code), ["<c>"]);
+    assert!(
+        headers(StdVersion::Cpp17, "import <a>;\nexport import <b>;")
+            .is_empty()
+    );
+}
+
+#[test]
+fn has_include() {
+    let code = "#if __has_include(<a>) && __has_include_next ( \"b\" )\n\
+                #elif defined(X) || __has_include(<c>)\n__has_include(<d>)";
+    assert_eq!(headers(StdVersion::Cpp17, code), ["<a>", "\"b\"", "<c>"]);
+}
+
+#[test]
+fn embed() {
+    let code = "#embed <a>\n#if __has_embed(\"b\")\n#endif";
+    assert_eq!(headers(StdVersion::C23, code), ["<a>", "\"b\""]);
+    assert_eq!(headers(StdVersion::Cpp26, code), ["<a>", "\"b\""]);
+    assert!(headers(StdVersion::C17, code).is_empty());
+}
+
+#[test]
+fn header_units() {
+    let code =
+        "import <a>;\nexport import \"b\";\n  import <c>;\nx import <d>;";
+    assert_eq!(headers(StdVersion::Cpp20, code), ["<a>", "\"b\"", "<c>"]);
+}
@@ -0,0 +1,148 @@
+use clowncc_token::{Cursor, Diagnostic};
+
+use clowncc_version::StdVersion;
+use expect_test::{expect, Expect};
+
+use std::fmt::Write;
+
+fn check_diagnostics(sv: StdVersion, code: &str, expect: Expect) {
+    let mut diags = Vec::new();
+    let mut sink = |diag: Diagnostic| diags.push(diag);
+    let mut cursor = Cursor::new(code, sv).with_diag_sink(&mut sink);
+    let mut total_len = 0;
+    while let Some(token) = cursor.next_token() {
+        total_len += token.length() as usize;
+    }
+    assert_eq!(code.len(), total_len);
+
+    let mut out = String::new();
+    for diag in diags {
+        let span = diag.span();
+        let text = &code[span.start() as usize..span.end() as usize];
+        writeln!(out, "{diag} {}..{} {text:?}", span.start(), span.end())
+            .unwrap();
+    }
+    expect.assert_eq(&out);
+}
+
+#[test]
+fn no_diagnostics() {
+    check_diagnostics(
+        StdVersion::Cpp26,
+        "int main() { return R\"x(a)x\"[0] + 'c' + 0x1p3; } // done",
+        expect![[""]],
+    );
+}
+
+#[test]
+fn stray_characters() {
+    check_diagnostics(
+        StdVersion::Cpp26,
+        "a @ \\ b $ 0x + 0b",
+        expect![[r#"
+        error[L0001]: unknown character in source 2..3 "@"
+        error[L0002]: stray '\' in source 4..5 "\\"
+        error[L0001]: unknown character in source 8..9 "$"
+        error[L0003]: numeric prefix without any digits 10..12 "0x"
+        error[L0003]: numeric prefix without any digits 15..17 "0b"
+    "#]],
+    );
+}
+
+#[test]
+fn unterminated() {
+    check_diagnostics(
+        StdVersion::Cpp26,
+        "\"abc\n'x\nint /* never closed",
+        expect![[r#"
+            error[L0005]: missing terminating '"' character 0..5 "\"abc\n"
+            error[L0006]: missing terminating '\'' character 5..8 "'x\n"
+            error[L0004]: unterminated block comment starting here 12..14 "/*"
+        "#]],
+    );
+}
+
+#[test]
+fn raw_strings() {
+    check_diagnostics(
+        StdVersion::Cpp26,
+        "R\"aaaaaaaaaaaaaaaaa\nR\" \nR\"ab\nR\"x(x)xx",
+        expect![[r#"
+            error[L0010]: raw string delimiter exceeds 16 characters 0..19 "R\"aaaaaaaaaaaaaaaaa"
+            error[L0008]: invalid character in raw string delimiter 20..23 "R\" "
+            error[L0009]: raw string delimiters must repeat a single character 24..27 "R\"a"
+            error[L0013]: unterminated raw string closing delimiter 29..37 "R\"x(x)xx"
+        "#]],
+    );
+    check_diagnostics(
+        StdVersion::Cpp26,
+        "R\"x(never",
+        expect![[r#"
+        error[L0011]: unterminated raw string 0..9 "R\"x(never"
+    "#]],
+    );
+    check_diagnostics(
+        StdVersion::Cpp26,
+        "R\"xx",
+        expect![[r#"
+        error[L0012]: unterminated raw string delimiter 0..4 "R\"xx"
+    "#]],
+    );
+}
+
+#[test]
+fn comment_warnings() {
+    check_diagnostics(
+        StdVersion::C23,
+        "/* a /* b */ // c \\\n d\nx",
+        expect![[r#"
+            warning[L0014]: '/*' within block comment 5..7 "/*"
+            warning[L0015]: multi-line '//' comment 18..20 "\\\n"
+        "#]],
+    );
+}
+
+#[test]
+fn universal_chars_in_identifiers() {
+    check_diagnostics(
+        StdVersion::Cpp26,
+        r"\u00e9t\u00e9 \u0301a a\u0301 a\u0041 \uD800 a\U00110000 \u0000",
+        expect![[r#"
+            error[L0025]: universal character name is not valid in an identifier 14..20 "\\u0301"
+            error[L0025]: universal character name is not valid in an identifier 31..37 "\\u0041"
+            error[L0020]: universal character name refers to an invalid character 38..44 "\\uD800"
+            error[L0020]: universal character name refers to an invalid character 46..56 "\\U00110000"
+            error[L0025]: universal character name is not valid in an identifier 57..63 "\\u0000"
+        "#]],
+    );
+    check_diagnostics(
+        StdVersion::C11,
+        r"\u0024 \u00C0 a\u0060 \u0041",
+        expect![[r#"
+            error[L0025]: universal character name is not valid in an identifier 0..6 "\\u0024"
+            error[L0025]: universal character name is not valid in an identifier 15..21 "\\u0060"
+            error[L0020]: universal character name refers to an invalid character 22..28 "\\u0041"
+        "#]],
+    );
+}
+
+#[test]
+fn raw_string_delimiters() {
+    check_diagnostics(
+        StdVersion::Cpp26,
+        "R\"$(a)$\" R\"\t(a)\t\"",
+        expect![[r#"
+            error[L0008]: invalid character in raw string delimiter 9..12 "R\"\t"
+            error[L0005]: missing terminating '"' character 16..17 "\""
+        "#]],
+    );
+    check_diagnostics(
+        StdVersion::Cpp23,
+        "R\"$(a)$\"",
+        expect![[r#"
+        error[L0008]: invalid character in raw string delimiter 0..3 "R\"$"
+        error[L0001]: unknown character in source 6..7 "$"
+        error[L0005]: missing terminating '"' character 7..8 "\""
+    "#]],
+    );
+}
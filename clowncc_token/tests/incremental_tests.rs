@@ -0,0 +1,96 @@
+use clowncc_token::{Cursor, Span, TextEdit, TokenBuffer};
+
+use clowncc_version::StdVersion;
+
+use std::ops::Range;
+
+/// Replaces `old` in `code` by `new_text` and checks that re-lexing the buffer
+/// matches lexing the edited source from scratch. Returns the texts of the
+/// tokens which were lexed again.
+fn check_relex(code: &str, old: &str, new_text: &str) -> Vec<String> {
+    let start = code.find(old).expect("edited text should be in the code");
+    check_relex_at(code, start..start + old.len(), new_text)
+}
+
+fn check_relex_at(
+    code: &str,
+    old: Range<usize>,
+    new_text: &str,
+) -> Vec<String> {
+    let sv = StdVersion::Cpp26;
+    let edited = [&code[..old.start], new_text, &code[old.end..]].concat();
+    let span = Span::new(old.start as u32, old.end as u32);
+    let edit = TextEdit::new(span, new_text.len() as u32);
+
+    let mut buffer = TokenBuffer::new();
+    buffer.tokenize(Cursor::new(code, sv));
+    let changed = buffer.relex(Cursor::new(&edited, sv), edit);
+
+    let mut expected = TokenBuffer::new();
+    expected.tokenize(Cursor::new(&edited, sv));
+    assert_eq!(buffer.kinds(), expected.kinds(), "{edited:?}");
+    assert_eq!(buffer.starts(), expected.starts(), "{edited:?}");
+    assert_eq!(buffer.lengths(), expected.lengths(), "{edited:?}");
+    changed
+        .map(|index| {
+            let span = buffer.span(index);
+            edited[span.start() as usize..span.end() as usize].to_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn edits_within_a_line() {
+    let code = "int a;\nint bc = 1;\nint d;\n";
+    assert_eq!(check_relex(code, "bc", "bcd"), ["\n", "int", " ", "bcd"]);
+    assert_eq!(
+        check_relex(code, "1", ""),
+        ["\n", "int", " ", "bc", " ", "=", " "]
+    );
+    assert_eq!(check_relex(code, "int a", "x"), ["x"]);
+    assert_eq!(check_relex(code, "d;\n", "e"), ["\n", "int", " ", "e"]);
+}
+
+#[test]
+fn edits_spanning_lines() {
+    let code = "a /* b */ c\nd\ne R\"x(f\n)x\" g\n";
+    let relexed = check_relex(code, "*/", "");
+    assert_eq!(relexed, ["a", " ", "/* b  c\nd\ne R\"x(f\n)x\" g\n"]);
+    let relexed = check_relex(code, "f", "h");
+    assert_eq!(relexed, ["\n", "e", " ", "R\"x(h\n)x\""]);
+    let relexed = check_relex(code, "d\n", "");
+    assert_eq!(relexed, ["\n"]);
+}
+
+#[test]
+fn edits_near_line_splices() {
+    let code = "ab\\ \nc x\\  y\nz";
+    assert_eq!(check_relex(code, "c", "\\\nc"), ["ab\\ \n\\\nc"]);
+    assert_eq!(check_relex(code, "y", "\ny"), ["ab\\ \nc", " ", "x\\  \ny"]);
+    assert_eq!(
+        check_relex(code, "\nz", "z"),
+        ["ab\\ \nc", " ", "x", "\\", "  ", "yz"]
+    );
+    assert_eq!(check_relex("a-\\\nb", "b", ">b"), ["a", "-\\\n>", "b"]);
+}
+
+#[test]
+fn edits_at_the_ends() {
+    assert_eq!(check_relex("a b", "a", "<"), ["<"]);
+    assert_eq!(check_relex("<:: b", "b", ">"), ["<", "::", " ", ">"]);
+    assert_eq!(check_relex("a\n", "\n", "\nb"), ["a", "\n", "b"]);
+    assert_eq!(check_relex("a", "a", ""), Vec::<String>::new());
+}
+
+#[test]
+fn every_single_char_edit() {
+    let code = "a/**/b \"c\\\"\" R\"(d)\"<::\\\n'e' // f\n1.2e+3";
+    for start in 0..=code.len() {
+        for new_text in ["", "/", "*", "\\", "\n", "\"", "'", "R", ")", "x"] {
+            check_relex_at(code, start..start, new_text);
+            if start < code.len() {
+                check_relex_at(code, start..start + 1, new_text);
+            }
+        }
+    }
+}
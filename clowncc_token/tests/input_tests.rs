@@ -0,0 +1,156 @@
+use clowncc_token::{
+    DiagCode, Diagnostic, InputCharset, InputDecoder, SourceInput, Span, Tokens,
+};
+
+use clowncc_version::StdVersion;
+use expect_test::{expect, Expect};
+
+use std::fmt::Write;
+
+fn decode(
+    bytes: &[u8],
+    charset: InputCharset,
+) -> (SourceInput<'_>, Vec<Diagnostic>) {
+    let mut diags = Vec::new();
+    let mut sink = |diag| diags.push(diag);
+    let input = InputDecoder::new(charset)
+        .with_diag_sink(&mut sink)
+        .decode(bytes);
+    (input, diags)
+}
+
+/// Prints each token with its text and span in the raw bytes.
+fn check_tokens(bytes: &[u8], charset: InputCharset, expect: Expect) {
+    let (input, diags) = decode(bytes, charset);
+    assert_eq!(diags, []);
+    let mut out = String::new();
+    let mut tokens = Tokens::new(input.cursor(StdVersion::Cpp26));
+    while let Some((token, span)) = tokens.next_spanned() {
+        let text = &input.text()[span.start() as usize..span.end() as usize];
+        let span = input.input_span(span);
+        let kind = token.kind();
+        let (start, end) = (span.start(), span.end());
+        writeln!(out, "{kind:?} {text:?} {start}..{end}").unwrap();
+    }
+    expect.assert_eq(&out);
+}
+
+#[test]
+fn utf8_byte_order_mark() {
+    let bytes = b"\xEF\xBB\xBFint \xC3\xA9;";
+    let (input, diags) = decode(bytes, InputCharset::Utf8);
+    assert_eq!(diags, []);
+    assert_eq!(input.text(), "int \u{e9};");
+    assert_eq!(input.bom_len(), 3);
+    check_tokens(
+        bytes,
+        InputCharset::Utf8,
+        expect![[r#"
+        Identifier { has_univ_char: false } "int" 3..6
+        Whitespace { splits_lines: false } " " 6..7
+        Identifier { has_univ_char: false } "é" 7..9
+        SemiColon ";" 9..10
+    "#]],
+    );
+}
+
+#[test]
+fn invalid_utf8() {
+    let bytes = b"a\xFF b\xE2\x82 c\xE2";
+    let (input, diags) = decode(bytes, InputCharset::Utf8);
+    assert_eq!(input.text(), "a\u{fffd} b\u{fffd} c\u{fffd}");
+    let spans: Vec<_> = diags.iter().map(|diag| diag.span()).collect();
+    assert_eq!(spans, [Span::new(1, 2), Span::new(4, 6), Span::new(8, 9)]);
+    assert!(diags.iter().all(|d| d.code() == DiagCode::InvalidEncoding));
+    assert_eq!(input.input_offset(4), 2);
+    assert_eq!(input.input_offset(6), 4);
+    assert_eq!(input.input_offset(9), 6);
+    assert_eq!(input.input_offset(input.text().len() as u32), 9);
+}
+
+#[test]
+fn latin1() {
+    let bytes = b"char c = '\xE9'; // caf\xE9\n";
+    let (input, diags) = decode(bytes, InputCharset::Latin1);
+    assert_eq!(diags, []);
+    assert_eq!(input.text(), "char c = '\u{e9}'; // caf\u{e9}\n");
+    check_tokens(
+        bytes,
+        InputCharset::Latin1,
+        expect![[r#"
+        Identifier { has_univ_char: false } "char" 0..4
+        Whitespace { splits_lines: false } " " 4..5
+        Identifier { has_univ_char: false } "c" 5..6
+        Whitespace { splits_lines: false } " " 6..7
+        Equal "=" 7..8
+        Whitespace { splits_lines: false } " " 8..9
+        CharSeq { lit_type: Default, has_esc: false } "'é'" 9..12
+        SemiColon ";" 12..13
+        Whitespace { splits_lines: false } " " 13..14
+        LineComment "// café" 14..21
+        Whitespace { splits_lines: true } "\n" 21..22
+    "#]],
+    );
+}
+
+#[test]
+fn utf16() {
+    let code = "int \u{e9} = U'\u{1F600}';\n";
+    let le: Vec<u8> = [0xFEFF]
+        .into_iter()
+        .chain(code.encode_utf16())
+        .flat_map(u16::to_le_bytes)
+        .collect();
+    let be: Vec<u8> = code.encode_utf16().flat_map(u16::to_be_bytes).collect();
+    let (input, diags) = decode(&be, InputCharset::Utf16);
+    assert_eq!((input.text(), input.bom_len()), (code, 0));
+    assert_eq!(diags, []);
+    let (input, diags) = decode(&le, InputCharset::Utf16);
+    assert_eq!((input.text(), input.bom_len()), (code, 2));
+    assert_eq!(diags, []);
+    check_tokens(
+        &le,
+        InputCharset::Utf16,
+        expect![[r#"
+        Identifier { has_univ_char: false } "int" 2..8
+        Whitespace { splits_lines: false } " " 8..10
+        Identifier { has_univ_char: false } "é" 10..12
+        Whitespace { splits_lines: false } " " 12..14
+        Equal "=" 14..16
+        Whitespace { splits_lines: false } " " 16..18
+        CharSeq { lit_type: Utf32, has_esc: false } "U'😀'" 18..28
+        SemiColon ";" 28..30
+        Whitespace { splits_lines: true } "\n" 30..32
+    "#]],
+    );
+}
+
+#[test]
+fn invalid_utf16() {
+    // An unpaired low surrogate, then a trailing odd byte
+    let bytes = b"\xFF\xFEa\0\x00\xDCb\0c";
+    let (input, diags) = decode(bytes, InputCharset::Utf16);
+    assert_eq!(input.text(), "a\u{fffd}b\u{fffd}");
+    let spans: Vec<_> = diags.iter().map(|diag| diag.span()).collect();
+    assert_eq!(spans, [Span::new(4, 6), Span::new(8, 9)]);
+    assert_eq!(input.input_offset(4), 6);
+    assert_eq!(input.input_offset(5), 8);
+    assert_eq!(input.input_offset(input.text().len() as u32), 9);
+}
+
+#[test]
+fn borrows_valid_utf8() {
+    let bytes = b"int x;";
+    let input = InputDecoder::new(InputCharset::Utf8).decode(bytes);
+    assert_eq!(input.text().as_ptr(), bytes.as_ptr());
+    let input = InputDecoder::new(InputCharset::Latin1).decode(bytes);
+    assert_eq!(input.text().as_ptr(), bytes.as_ptr());
+}
+
+#[test]
+fn latin1_which_is_also_utf8() {
+    let bytes = b"caf\xC3\xA9";
+    let (input, diags) = decode(bytes, InputCharset::Latin1);
+    assert_eq!(diags, []);
+    assert_eq!(input.text(), "caf\u{c3}\u{a9}");
+}
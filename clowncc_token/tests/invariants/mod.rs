@@ -0,0 +1,134 @@
+//! Invariants every tokenization must uphold, shared by the property tests
+//! and the fuzz targets.
+
+use clowncc_token::{Cursor, Token, TokenKind};
+
+use clowncc_version::{LangOptions, StdVersion};
+
+/// Kind, length and flag bits of a token, which are comparable unlike
+/// [`Token`] itself.
+pub type TokenParts = (TokenKind, u32, u8);
+
+/// The options of `std_vers` with the switches which affect lexing set by the
+/// low bits of `switches`: bit 0 turns trigraphs on, bit 1 turns digraphs off
+/// and bit 2 flips whether `$` may appear in identifiers.
+pub fn lang_options(std_vers: StdVersion, switches: u8) -> LangOptions {
+    let opts = LangOptions::new(std_vers);
+    let dollars = opts.has_dollars_in_identifiers() != (switches & 4 != 0);
+    opts.with_trigraphs(switches & 1 != 0)
+        .with_digraphs(opts.has_digraphs() && switches & 2 == 0)
+        .with_dollars_in_identifiers(dollars)
+}
+
+/// Tokenizes `code` with `next_token`, or `next_token_header` if `header` is
+/// set, and panics if any invariant does not hold.
+pub fn check_tokens(
+    code: &str,
+    lang_opts: LangOptions,
+    header: bool,
+) -> Vec<TokenParts> {
+    let tokens = lex(code, lang_opts, header);
+    let mut offset = 0;
+    let mut texts = Vec::with_capacity(tokens.len());
+    for &(kind, length, _) in &tokens {
+        let end = offset + length as usize;
+        assert!(length > 0, "empty {kind:?} at {offset} in {code:?}");
+        assert!(end <= code.len(), "{kind:?} overruns {code:?}");
+        assert!(code.is_char_boundary(end), "{kind:?} splits a char");
+        texts.push(&code[offset..end]);
+        offset = end;
+    }
+    assert_eq!(offset, code.len(), "tokens do not cover {code:?}");
+
+    let mut cursor = Cursor::new(code, lang_opts);
+    for text in texts.iter().copied() {
+        let token = next(&mut cursor, header).unwrap();
+        check_token(&token, text);
+    }
+
+    // A token never depends on the source after it, so lexing the text of
+    // each token on its own and joining the results finds the same tokens
+    let relexed: Vec<_> = texts
+        .iter()
+        .flat_map(|text| lex(text, lang_opts, header))
+        .map(erase_raw_str_err)
+        .collect();
+    let expected: Vec<_> =
+        tokens.iter().copied().map(erase_raw_str_err).collect();
+    assert_eq!(relexed, expected, "{code:?}");
+    tokens
+}
+
+/// Checks the invariants of a single token against its source text.
+pub fn check_token(token: &Token, text: &str) {
+    let (kind, flags) = (token.kind(), token.flags());
+    assert_eq!(token.length() as usize, text.len());
+    assert!(
+        !kind.is_single_char()
+            || text.len() == 1
+            || flags.is_alt_spelling()
+            || flags.has_new_line(),
+        "single char {kind:?} spelled {text:?}",
+    );
+    assert!(
+        kind.is_delimited() || !flags.is_unterminated(),
+        "undelimited {kind:?} is unterminated: {text:?}",
+    );
+    assert_eq!(
+        flags.has_new_line(),
+        text.contains('\n'),
+        "newline flag of {kind:?} {text:?}",
+    );
+    // Unterminated quoted tokens consume the newline which ends them
+    let body = match text.strip_suffix('\n') {
+        Some(body) if flags.is_unterminated() => body,
+        _ => text,
+    };
+    // The backslash of a splice may be spelled as the trigraph `??/`
+    let has_backslash = body.contains('\\')
+        || (flags.has_trigraph() && body.contains("??/"));
+    assert!(
+        kind.is_multi_line() || !body.contains('\n') || has_backslash,
+        "{kind:?} spans lines without a splice: {text:?}",
+    );
+    if kind.is_delimited() && !flags.is_unterminated() && !flags.has_ud_suffix()
+    {
+        let close = match kind {
+            TokenKind::BlockComment => '/',
+            TokenKind::SystemHeader => '>',
+            TokenKind::CharSeq { .. } => '\'',
+            _ => '"',
+        };
+        assert!(
+            text.ends_with(close),
+            "terminated {kind:?} does not end with {close:?}: {text:?}",
+        );
+    }
+}
+
+/// The error of a bad raw string is found from the character which stopped
+/// it, which is after the token, so only compare that there was an error.
+fn erase_raw_str_err(
+    (kind, length, flags): TokenParts,
+) -> (Option<TokenKind>, u32, u8) {
+    let kind = match kind {
+        TokenKind::BadRawStr(_) => None,
+        _ => Some(kind),
+    };
+    (kind, length, flags)
+}
+
+fn next(cursor: &mut Cursor<'_>, header: bool) -> Option<Token> {
+    if header {
+        cursor.next_token_header()
+    } else {
+        cursor.next_token()
+    }
+}
+
+fn lex(code: &str, lang_opts: LangOptions, header: bool) -> Vec<TokenParts> {
+    let mut cursor = Cursor::new(code, lang_opts);
+    std::iter::from_fn(|| next(&mut cursor, header))
+        .map(|token| (token.kind(), token.length(), token.flags().bits()))
+        .collect()
+}
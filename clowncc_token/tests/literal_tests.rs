@@ -0,0 +1,249 @@
+use clowncc_token::{LitDecoder, LitType};
+
+use clowncc_version::{LangOptions, StdVersion, TargetInfo};
+use expect_test::{expect, Expect};
+
+use std::fmt::Write;
+
+fn check_decoder(decoder: LitDecoder<'_>, text: &str, expect: Expect) {
+    let mut out = String::new();
+    let mut units = Vec::new();
+    for item in decoder {
+        match item {
+            Ok(unit) => units.push(format!("{unit:02x}")),
+            Err(diag) => {
+                if !units.is_empty() {
+                    writeln!(out, "{}", units.join(" ")).unwrap();
+                    units.clear();
+                }
+                let span = diag.span();
+                let range = span.start() as usize..span.end() as usize;
+                writeln!(out, "{diag} {:?}", &text[range]).unwrap();
+            }
+        }
+    }
+    out.push_str(&units.join(" "));
+    expect.assert_eq(out.trim_end());
+}
+
+fn check_literal(
+    sv: StdVersion,
+    lit_type: LitType,
+    text: &str,
+    expect: Expect,
+) {
+    check_decoder(LitDecoder::new(text, lit_type, sv), text, expect);
+}
+
+#[test]
+fn simple_escapes() {
+    check_literal(
+        StdVersion::Cpp26,
+        LitType::Default,
+        r#""a\'\"\?\\\a\b\f\n\r\t\v""#,
+        expect!["61 27 22 3f 5c 07 08 0c 0a 0d 09 0b"],
+    );
+}
+
+#[test]
+fn numeric_escapes() {
+    check_literal(
+        StdVersion::C17,
+        LitType::Default,
+        r#""\0\101\1234\x41\x00041g""#,
+        expect!["00 41 53 34 41 41 67"],
+    );
+    check_literal(
+        StdVersion::C17,
+        LitType::Default,
+        r#"'\777\x100\xg'"#,
+        expect![[r#"
+            error[L0017]: escape sequence out of range "\\777"
+            error[L0017]: escape sequence out of range "\\x100"
+            error[L0018]: '\x' used with no following hex digits "\\x"
+            67"#]],
+    );
+    check_literal(
+        StdVersion::Cpp26,
+        LitType::Utf16,
+        r#"u"\xffff\x10000\x100000000""#,
+        expect![[r#"
+            ffff
+            error[L0017]: escape sequence out of range "\\x10000"
+            error[L0017]: escape sequence out of range "\\x100000000""#]],
+    );
+}
+
+#[test]
+fn encodings() {
+    check_literal(
+        StdVersion::Cpp26,
+        LitType::Utf16,
+        "u\"\u{e9}\\u00e9\\U0001F600\"",
+        expect!["e9 e9 d83d de00"],
+    );
+    check_literal(
+        StdVersion::Cpp26,
+        LitType::Utf8,
+        "u8\"\u{e9}\\u00e9\\U0001F600\"",
+        expect!["c3 a9 c3 a9 f0 9f 98 80"],
+    );
+    check_literal(
+        StdVersion::Cpp26,
+        LitType::Wide,
+        "L\"\u{e9}\\u00e9\\U0001F600\"",
+        expect!["e9 e9 1f600"],
+    );
+}
+
+#[test]
+fn wide_target() {
+    let text = "L\"\u{e9}\\U0001F600\\xffff\\x10000\"";
+    let windows: TargetInfo = "x86_64-pc-windows-msvc".parse().unwrap();
+    let decoder = LitDecoder::new(text, LitType::Wide, StdVersion::Cpp26)
+        .with_target(&windows);
+    check_decoder(
+        decoder,
+        text,
+        expect![[r#"
+            e9 d83d de00 ffff
+            error[L0017]: escape sequence out of range "\\x10000""#]],
+    );
+    let decoder = LitDecoder::new(text, LitType::Wide, StdVersion::Cpp26)
+        .with_target(&TargetInfo::ARM_NONE_EABI);
+    check_decoder(decoder, text, expect!["e9 1f600 ffff 10000"]);
+}
+
+#[test]
+fn universal_chars() {
+    check_literal(
+        StdVersion::C23,
+        LitType::Utf32,
+        r#"U"\u0024\u0041\uD800\U00110000\u12""#,
+        expect![[r#"
+            24
+            error[L0020]: universal character name refers to an invalid character "\\u0041"
+            error[L0020]: universal character name refers to an invalid character "\\uD800"
+            error[L0020]: universal character name refers to an invalid character "\\U00110000"
+            error[L0019]: incomplete universal character name "\\u12""#]],
+    );
+    check_literal(
+        StdVersion::Cpp11,
+        LitType::Utf32,
+        r#"U"\u0024\u0041""#,
+        expect!["24 41"],
+    );
+    check_literal(
+        StdVersion::C89,
+        LitType::Default,
+        r#""\u0041""#,
+        expect![[r#"
+            warning[L0016]: unknown escape sequence "\\u"
+            75 30 30 34 31"#]],
+    );
+}
+
+#[test]
+fn delimited_escapes() {
+    check_literal(
+        StdVersion::Cpp23,
+        LitType::Utf32,
+        r#"U"\o{101}\x{41}\u{1F600}\x{}\x{1g}\u{D800}\x{100000000}""#,
+        expect![[r#"
+            41 41 1f600
+            error[L0022]: delimited escape sequence with empty braces "\\x{}"
+            error[L0023]: unterminated delimited escape sequence "\\x{1"
+            67 7d
+            error[L0020]: universal character name refers to an invalid character "\\u{D800}"
+            error[L0017]: escape sequence out of range "\\x{100000000}""#]],
+    );
+    check_literal(
+        StdVersion::Cpp20,
+        LitType::Utf32,
+        r#"U"\o{101}\x{41}""#,
+        expect![[r#"
+            warning[L0016]: unknown escape sequence "\\o"
+            6f 7b 31 30 31 7d
+            error[L0018]: '\x' used with no following hex digits "\\x"
+            7b 34 31 7d"#]],
+    );
+}
+
+#[test]
+fn named_escapes() {
+    check_literal(
+        StdVersion::Cpp23,
+        LitType::Default,
+        r#""\N{LATIN SMALL LETTER A}\N{DIGIT SEVEN}\N{TILDE}\N{NOPE}\N{}\N""#,
+        expect![[r#"
+            61 37 7e
+            error[L0024]: unknown character name "\\N{NOPE}"
+            error[L0022]: delimited escape sequence with empty braces "\\N{}"
+            error[L0021]: expected '{' after '\N' escape sequence "\\N""#]],
+    );
+    check_literal(
+        StdVersion::Cpp23,
+        LitType::Utf32,
+        r#""\N{LATIN SMALL LETTER E WITH ACUTE}\N{SNOWMAN}\N{LINE FEED}""#,
+        expect!["e9 2603 0a"],
+    );
+    let text = r#""\N{SNOWMAN}""#;
+    let decoder = LitDecoder::new(text, LitType::Utf32, StdVersion::Cpp23)
+        .with_name_lookup(|name| (name == "SNOWMAN").then_some('\u{2604}'));
+    check_decoder(decoder, text, expect!["2604"]);
+}
+
+#[test]
+fn unknown_escapes() {
+    check_literal(
+        StdVersion::Cpp26,
+        LitType::Default,
+        r#""\q\N\e""#,
+        expect![[r#"
+            warning[L0016]: unknown escape sequence "\\q"
+            71
+            error[L0021]: expected '{' after '\N' escape sequence "\\N"
+            warning[L0016]: unknown escape sequence "\\e"
+            65"#]],
+    );
+}
+
+#[test]
+fn line_splices() {
+    check_literal(
+        StdVersion::Cpp26,
+        LitType::Default,
+        "\"a\\\nb\\ \n\\\\\nx41\"",
+        expect!["61 62 41"],
+    );
+}
+
+#[test]
+fn raw_strings_and_suffixes() {
+    check_literal(
+        StdVersion::Cpp26,
+        LitType::Default,
+        "R\"x(a\\n)\"\\\n)x\"_sv",
+        expect!["61 5c 6e 29 22 5c 0a"],
+    );
+    check_literal(
+        StdVersion::Cpp26,
+        LitType::Utf16,
+        "u'\\x41'_c",
+        expect!["41"],
+    );
+}
+
+#[test]
+fn trigraphs() {
+    let text = r#""??/n??=""#;
+    let trigraphs = |sv| LangOptions::new(sv).with_trigraphs(true);
+    let decoder =
+        LitDecoder::new(text, LitType::Default, trigraphs(StdVersion::C17));
+    check_decoder(decoder, text, expect!["0a 23"]);
+    let decoder =
+        LitDecoder::new(text, LitType::Default, trigraphs(StdVersion::C23));
+    check_decoder(decoder, text, expect!["3f 3f 2f 6e 3f 3f 3d"]);
+    let decoder = LitDecoder::new(text, LitType::Default, StdVersion::C17);
+    check_decoder(decoder, text, expect!["3f 3f 2f 6e 3f 3f 3d"]);
+}
@@ -0,0 +1,73 @@
+use clowncc_token::{Cursor, PosCursor};
+
+use clowncc_version::StdVersion;
+use expect_test::{expect, Expect};
+
+use std::fmt::Write;
+
+fn check_positions(code: &str, expect: Expect) {
+    let mut cursor = PosCursor::new(Cursor::new(code, StdVersion::Cpp26));
+    let mut positions = String::new();
+    while let Some((token, pos)) = cursor.next_token() {
+        let start = pos.offset() as usize;
+        let text = &code[start..start + token.length() as usize];
+        writeln!(positions, "{:?} {:?} {:?}", pos, token.kind(), text).unwrap();
+    }
+    expect.assert_eq(&positions);
+    assert_eq!(code.len(), cursor.pos().offset() as usize);
+}
+
+#[test]
+fn lines_and_columns() {
+    check_positions(
+        "int x;\n  x = 1;\n",
+        expect![[r#"
+            1:1 @ 0 Identifier { has_univ_char: false } "int"
+            1:4 @ 3 Whitespace { splits_lines: false } " "
+            1:5 @ 4 Identifier { has_univ_char: false } "x"
+            1:6 @ 5 SemiColon ";"
+            1:7 @ 6 Whitespace { splits_lines: true } "\n  "
+            2:3 @ 9 Identifier { has_univ_char: false } "x"
+            2:4 @ 10 Whitespace { splits_lines: false } " "
+            2:5 @ 11 Equal "="
+            2:6 @ 12 Whitespace { splits_lines: false } " "
+            2:7 @ 13 Number(NumberInfo { base: Decimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 0, has_long_suffix: false }) "1"
+            2:8 @ 14 SemiColon ";"
+            2:9 @ 15 Whitespace { splits_lines: true } "\n"
+        "#]],
+    );
+}
+
+#[test]
+fn escaped_newlines() {
+    check_positions(
+        "a = \"b\\\nc\"; /* x\ny */ d",
+        expect![[r#"
+            1:1 @ 0 Identifier { has_univ_char: false } "a"
+            1:2 @ 1 Whitespace { splits_lines: false } " "
+            1:3 @ 2 Equal "="
+            1:4 @ 3 Whitespace { splits_lines: false } " "
+            1:5 @ 4 Str { lit_type: Default, has_esc: true } "\"b\\\nc\""
+            2:3 @ 10 SemiColon ";"
+            2:4 @ 11 Whitespace { splits_lines: false } " "
+            2:5 @ 12 BlockComment "/* x\ny */"
+            3:5 @ 21 Whitespace { splits_lines: false } " "
+            3:6 @ 22 Identifier { has_univ_char: false } "d"
+        "#]],
+    );
+}
+
+#[test]
+fn multi_byte_columns() {
+    check_positions(
+        "\u{e9}t\u{e9} = \"\u{1F600}\";",
+        expect![[r#"
+            1:1 @ 0 Identifier { has_univ_char: false } "été"
+            1:4 @ 5 Whitespace { splits_lines: false } " "
+            1:5 @ 6 Equal "="
+            1:6 @ 7 Whitespace { splits_lines: false } " "
+            1:7 @ 8 Str { lit_type: Default, has_esc: false } "\"😀\""
+            1:10 @ 14 SemiColon ";"
+        "#]],
+    );
+}
@@ -0,0 +1,148 @@
+use clowncc_token::{needs_separator, print_tokens, Cursor, PrintMode, Tokens};
+
+use clowncc_version::{LangOptions, StdVersion};
+use expect_test::{expect, Expect};
+
+use std::fmt::Write;
+
+fn print(cursor: Cursor<'_>, mode: PrintMode) -> String {
+    let mut out = String::new();
+    print_tokens(&mut out, cursor, mode).unwrap();
+    out
+}
+
+fn check_canonical(sv: StdVersion, code: &str, expect: Expect) {
+    let cursor = Cursor::new(code, LangOptions::new(sv).with_trigraphs(true));
+    expect.assert_eq(&print(cursor, PrintMode::Canonical));
+}
+
+#[test]
+fn lossless_round_trip() {
+    let sources = [
+        "",
+        "int main() { return 0; }\n",
+        "  /* a */ // b \\\n c\n\n#define X(a) a ## \\\n  b\n",
+        "??=define S \"a??/\"b\" ??< <: %:%: ??>",
+        "R\"x(raw\\\n)x\" u8'c' L\"\\\n\" 1.2e+3_km @ \\",
+        "\"unterminated\n'c /* never closed",
+    ];
+    for sv in [StdVersion::C89, StdVersion::C17, StdVersion::Cpp14] {
+        for code in sources {
+            let cursor =
+                Cursor::new(code, LangOptions::new(sv).with_trigraphs(true));
+            assert_eq!(print(cursor, PrintMode::Lossless), code);
+        }
+    }
+}
+
+#[test]
+fn canonical_whitespace_and_comments() {
+    check_canonical(
+        StdVersion::C17,
+        "\n\n  int\tx /* a */ =\n\n\t1; // b\n/* c\n */ y\n\n",
+        expect![[r#"
+            int x =
+            1;
+            y
+        "#]],
+    );
+}
+
+#[test]
+fn canonical_spellings() {
+    check_canonical(
+        StdVersion::Cpp14,
+        "%:def\\\nine A<:1:> <% %> ??= ??( in\\\nt \"a??/n\\\nb\" R\"(\\\n)\"",
+        expect![[r##"
+            #define A[1] { } # [ int "a\nb" R"(\
+            )""##]],
+    );
+}
+
+/// Whether each pair of adjacent tokens in `code` needs a separator, ignoring
+/// whitespace so that `a b` checks if `a` and `b` can be pasted together.
+fn separators(sv: StdVersion, code: &str) -> Vec<(&str, &str, bool)> {
+    let tokens: Vec<_> = Tokens::new(Cursor::new(code, sv))
+        .filter(|(token, _)| !token.kind().is_trivia())
+        .collect();
+    let pairs = tokens.windows(2).map(|pair| {
+        let [(left, left_text), (right, right_text)] = pair else {
+            unreachable!()
+        };
+        let needs = needs_separator((left, left_text), (right, right_text), sv);
+        (*left_text, *right_text, needs)
+    });
+    pairs.collect()
+}
+
+fn check_separators(sv: StdVersion, code: &str, expect: Expect) {
+    let mut out = String::new();
+    for (left_text, right_text, needs) in separators(sv, code) {
+        writeln!(out, "{left_text:?} {right_text:?}: {needs}").unwrap();
+    }
+    expect.assert_eq(&out);
+}
+
+#[test]
+fn separators_between_tokens() {
+    check_separators(
+        StdVersion::Cpp26,
+        "a b 1 . 2 - > < : : u8 \"s\" _x 1e + 1 ) ( . . / / * ??= ! =",
+        expect![[r#"
+            "a" "b": true
+            "b" "1": true
+            "1" ".": true
+            "." "2": true
+            "2" "-": false
+            "-" ">": true
+            ">" "<": false
+            "<" ":": true
+            ":" ":": true
+            ":" "u8": false
+            "u8" "\"s\"": true
+            "\"s\"" "_x": true
+            "_x" "1e": true
+            "1e" "+": true
+            "+" "1": false
+            "1" ")": false
+            ")" "(": false
+            "(" ".": false
+            "." ".": false
+            "." "/": false
+            "/" "/": true
+            "/" "*": true
+            "*" "?": false
+            "?" "?": false
+            "?" "=": false
+            "=" "!": false
+            "!" "=": true
+        "#]],
+    );
+    check_separators(
+        StdVersion::C17,
+        "u8 'c' R \"x\" < : : ?? = a",
+        expect![[r#"
+            "u8" "'c'": false
+            "'c'" "R": false
+            "R" "\"x\"": false
+            "\"x\"" "<": false
+            "<" ":": true
+            ":" ":": false
+            ":" "?": false
+            "?" "?": false
+            "?" "=": false
+            "=" "a": false
+        "#]],
+    );
+}
+
+#[test]
+fn separators_between_long_tokens() {
+    let name = "x".repeat(300);
+    let code = format!("{name} {name} \"{name}\" _s - >");
+    let needs: Vec<_> = separators(StdVersion::Cpp26, &code)
+        .into_iter()
+        .map(|(_, _, needs)| needs)
+        .collect();
+    assert_eq!(needs, [true, false, true, false, true]);
+}
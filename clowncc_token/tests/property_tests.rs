@@ -0,0 +1,113 @@
+mod invariants;
+
+use invariants::{check_tokens, lang_options};
+
+use clowncc_version::StdVersion;
+use proptest::prelude::*;
+use strum::IntoEnumIterator;
+
+/// Pieces of source which start or end the trickier tokens.
+const FRAGMENTS: &[&str] = &[
+    " ",
+    "\t",
+    "\n",
+    "\r\n",
+    "\\\n",
+    "\\ \n",
+    "??/\n",
+    "??=",
+    "??(",
+    "??'",
+    "/*",
+    "*/",
+    "//",
+    "#",
+    "%:%:",
+    "<:",
+    "<::",
+    ":>",
+    "<%",
+    "...",
+    "->*",
+    "<=>",
+    "'",
+    "\"",
+    "R\"",
+    "R\"x(",
+    ")x\"",
+    "u8",
+    "u",
+    "U",
+    "L",
+    "_x",
+    "0x",
+    "0b",
+    "1",
+    "1.e+",
+    "'0'",
+    "\\u00e9",
+    "\\U0001F600",
+    "\\N{",
+    "}",
+    "\\x",
+    "<",
+    ">",
+    "a",
+    "\u{e9}",
+    "\u{1F600}",
+    "$",
+    "@",
+    "\u{0}",
+];
+
+fn fragments() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(FRAGMENTS), 0..24)
+        .prop_map(|pieces| pieces.concat())
+}
+
+/// Switches for [`lang_options`], which are usually left at the defaults.
+fn switches() -> impl Strategy<Value = u8> {
+    prop_oneof![3 => Just(0), 1 => 0..8u8]
+}
+
+fn check_all_versions(code: &str, switches: u8) {
+    for sv in StdVersion::iter() {
+        let lang_opts = lang_options(sv, switches);
+        check_tokens(code, lang_opts, false);
+        check_tokens(code, lang_opts, true);
+    }
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_text(code in any::<String>(), switches in switches()) {
+        check_all_versions(&code, switches);
+    }
+
+    #[test]
+    fn ascii_text(code in "[ -~\t\n]{0,48}", switches in switches()) {
+        check_all_versions(&code, switches);
+    }
+
+    #[test]
+    fn joined_fragments(code in fragments(), switches in switches()) {
+        check_all_versions(&code, switches);
+    }
+}
+
+#[test]
+fn tricky_sources() {
+    for code in [
+        "",
+        "a\\\n",
+        "\"\\\\\" R\"x(\n)x\"_s",
+        "#include <a.h> /* b\n*/ c",
+        "u8'\u{e9}' U\"??/\n\" ??=??=",
+        "1.2e+3_km .5 0x1p-2 0b1'0",
+        "$a ??/\n<: %:%: a$",
+    ] {
+        for switches in 0..8 {
+            check_all_versions(code, switches);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use clowncc_token::{Cursor, Span, TokenBuffer, TokenKind, Tokens};
+
+use clowncc_version::StdVersion;
+use expect_test::{expect, Expect};
+
+use std::fmt::Write;
+
+fn check_tokens(code: &str, expect: Expect) {
+    let mut out = String::new();
+    let mut tokens = Tokens::new(Cursor::new(code, StdVersion::Cpp26));
+    for (token, text) in tokens.by_ref() {
+        writeln!(out, "{:?} {text:?}", token.kind()).unwrap();
+    }
+    expect.assert_eq(&out);
+    assert_eq!(tokens.offset() as usize, code.len());
+    assert_eq!(tokens.remaining(), "");
+}
+
+#[test]
+fn token_slices() {
+    check_tokens(
+        "int x = u8\"\u{e9}\"; // done",
+        expect![[r#"
+        Identifier { has_univ_char: false } "int"
+        Whitespace { splits_lines: false } " "
+        Identifier { has_univ_char: false } "x"
+        Whitespace { splits_lines: false } " "
+        Equal "="
+        Whitespace { splits_lines: false } " "
+        Str { lit_type: Utf8, has_esc: false } "u8\"é\""
+        SemiColon ";"
+        Whitespace { splits_lines: false } " "
+        LineComment "// done"
+    "#]],
+    );
+}
+
+#[test]
+fn headers_and_spans() {
+    let code = "#include <a.h>\nx";
+    let mut tokens = Tokens::new(Cursor::new(code, StdVersion::C17));
+    assert_eq!(tokens.next_spanned().unwrap().1, Span::new(0, 1));
+    assert_eq!(tokens.next().unwrap().1, "include");
+    assert_eq!(tokens.next().unwrap().1, " ");
+    let (token, text) = tokens.next_header().unwrap();
+    assert_eq!(token.kind(), TokenKind::SystemHeader);
+    assert_eq!(text, "<a.h>");
+    assert_eq!(tokens.remaining(), "\nx");
+}
+
+#[test]
+fn token_buffer() {
+    let code = "a += 0x1p3;\n/* c */";
+    let mut buffer = TokenBuffer::new();
+    buffer.tokenize(Cursor::new(code, StdVersion::C23));
+
+    let tokens: Vec<_> = Tokens::new(Cursor::new(code, StdVersion::C23))
+        .map(|(token, text)| (token.kind(), text))
+        .collect();
+    assert_eq!(buffer.len(), tokens.len());
+    assert_eq!(buffer.kinds().len(), buffer.lengths().len());
+    for (index, &(kind, text)) in tokens.iter().enumerate() {
+        let span = buffer.span(index);
+        assert_eq!(buffer.kinds()[index], kind);
+        assert_eq!(buffer.token(index).kind(), kind);
+        assert_eq!(&code[span.start() as usize..span.end() as usize], text);
+    }
+    assert_eq!(
+        buffer.iter().map(|t| t.length()).sum::<u32>() as usize,
+        code.len()
+    );
+
+    buffer.tokenize(Cursor::new("x", StdVersion::C23));
+    assert_eq!(buffer.len(), 1);
+    assert_eq!(buffer.starts(), &[0]);
+    buffer.clear();
+    assert!(buffer.is_empty());
+}
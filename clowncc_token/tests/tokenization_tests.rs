@@ -1,6 +1,6 @@
 use clowncc_token::{Cursor, Token};
 
-use clowncc_version::StdVersion;
+use clowncc_version::{LangOptions, StdDialect, StdVersion};
 use expect_test::{expect, Expect};
 
 use std::fmt::Write;
@@ -8,14 +8,22 @@ fn check_tokens_impl<'c>(
     sv: StdVersion,
     code: &'c str,
     expect: Expect,
+    tok_fn: impl FnMut(&mut Cursor<'c>) -> Option<Token>,
+) {
+    check_cursor_tokens(Cursor::new(code, sv), code, expect, tok_fn);
+}
+
+fn check_cursor_tokens<'c>(
+    mut cursor: Cursor<'c>,
+    code: &'c str,
+    expect: Expect,
     mut tok_fn: impl FnMut(&mut Cursor<'c>) -> Option<Token>,
 ) {
-    let mut cursor = Cursor::new(code, sv);
     let mut total_len = 0;
     let mut tokens = String::new();
     for t in core::iter::from_fn(|| tok_fn(&mut cursor)) {
         total_len += t.length();
-        write!(tokens, "{:?}\n", t).unwrap();
+        writeln!(tokens, "{:?}", t).unwrap();
     }
     expect.assert_eq(&tokens);
     assert_eq!(code.len(), total_len.try_into().unwrap());
@@ -29,6 +37,11 @@ fn check_header_tokens(sv: StdVersion, code: &str, expect: Expect) {
     check_tokens_impl(sv, code, expect, Cursor::next_token_header);
 }
 
+fn check_trigraph_tokens(sv: StdVersion, code: &str, expect: Expect) {
+    let cursor = Cursor::new(code, LangOptions::new(sv).with_trigraphs(true));
+    check_cursor_tokens(cursor, code, expect, Cursor::next_token);
+}
+
 #[test]
 fn hello_world_test() {
     check_basic_tokens(
@@ -82,7 +95,7 @@ u\
             Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
             Token { kind: Equal, length: 1, flags: TokenFlags(0x0) }
             Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
-            Token { kind: Number { base: Decimal, has_sep: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 0, has_long_suffix: false }), length: 1, flags: TokenFlags(0x0) }
             Token { kind: SemiColon, length: 1, flags: TokenFlags(0x0) }
             Token { kind: Whitespace { splits_lines: true }, length: 1, flags: TokenFlags(NEWLINE) }
         "#]],
@@ -157,8 +170,7 @@ fn number_with_separators_enabled() {
             Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
             Token { kind: Equal, length: 1, flags: TokenFlags(0x0) }
             Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
-            Token { kind: Number { base: Hexidecimal, has_sep: true }, length: 14, flags: TokenFlags(0x0) }
-            Token { kind: Identifier { has_univ_char: false }, length: 3, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Hexidecimal, has_sep: true, is_float: false, has_exponent: false, suffix_len: 3, has_long_suffix: false }), length: 17, flags: TokenFlags(0x0) }
             Token { kind: SemiColon, length: 1, flags: TokenFlags(0x0) }
         "#]],
     );
@@ -176,12 +188,10 @@ fn number_with_separators_disabled() {
             Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
             Token { kind: Equal, length: 1, flags: TokenFlags(0x0) }
             Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
-            Token { kind: Number { base: Hexidecimal, has_sep: false }, length: 3, flags: TokenFlags(0x0) }
-            Token { kind: CharSeq { lit_type: Default, has_esc: false }, length: 3, flags: TokenFlags(0x0) }
-            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Hexidecimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 0, has_long_suffix: false }), length: 3, flags: TokenFlags(0x0) }
+            Token { kind: CharSeq { lit_type: Default, has_esc: false }, length: 4, flags: TokenFlags(UD_SUFFIX) }
             Token { kind: CharSeq { lit_type: Default, has_esc: false }, length: 6, flags: TokenFlags(0x0) }
-            Token { kind: Number { base: Decimal, has_sep: false }, length: 1, flags: TokenFlags(0x0) }
-            Token { kind: Identifier { has_univ_char: false }, length: 3, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 3, has_long_suffix: false }), length: 4, flags: TokenFlags(0x0) }
             Token { kind: SemiColon, length: 1, flags: TokenFlags(0x0) }
         "#]],
     );
@@ -230,3 +240,530 @@ fn spliced_system_header() {
         "#]],
     );
 }
+
+#[test]
+fn multi_char_punctuators() {
+    check_basic_tokens(
+        StdVersion::Cpp26,
+        "a->*b.*c->d...<<=>>=<=>::&&||##++--!=^^",
+        expect![[r#"
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: ArrowStar, length: 3, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: DotStar, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Arrow, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Ellipsis, length: 3, flags: TokenFlags(0x0) }
+            Token { kind: LessThanLessThanEqual, length: 3, flags: TokenFlags(0x0) }
+            Token { kind: GreaterThanGreaterThanEqual, length: 3, flags: TokenFlags(0x0) }
+            Token { kind: Spaceship, length: 3, flags: TokenFlags(0x0) }
+            Token { kind: ColonColon, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: AmpersandAmpersand, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: PipePipe, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: PoundPound, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: PlusPlus, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: MinusMinus, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: ExclamationEqual, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: CaretCaret, length: 2, flags: TokenFlags(0x0) }
+        "#]],
+    );
+}
+
+#[test]
+fn spaceship_before_cpp20() {
+    check_basic_tokens(
+        StdVersion::Cpp17,
+        "a<=>b",
+        expect![[r#"
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: LessThanEqual, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: GreaterThan, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+        "#]],
+    );
+}
+
+#[test]
+fn c_only_punctuators() {
+    check_basic_tokens(
+        StdVersion::C17,
+        "a::b.*c->*d",
+        expect![[r#"
+        Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Colon, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Colon, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Dot, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Star, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Arrow, length: 2, flags: TokenFlags(0x0) }
+        Token { kind: Star, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+    "#]],
+    );
+    check_basic_tokens(
+        StdVersion::C23,
+        "[[gnu::pure]]",
+        expect![[r#"
+        Token { kind: OpenBracket, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: OpenBracket, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Identifier { has_univ_char: false }, length: 3, flags: TokenFlags(0x0) }
+        Token { kind: ColonColon, length: 2, flags: TokenFlags(0x0) }
+        Token { kind: Identifier { has_univ_char: false }, length: 4, flags: TokenFlags(0x0) }
+        Token { kind: CloseBracket, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: CloseBracket, length: 1, flags: TokenFlags(0x0) }
+    "#]],
+    );
+}
+
+#[test]
+fn punctuators_maximal_munch() {
+    check_basic_tokens(
+        StdVersion::Cpp26,
+        "x+++++y..z<<<=>>>",
+        expect![[r#"
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: PlusPlus, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: PlusPlus, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: Plus, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Dot, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Dot, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: LessThanLessThan, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: Spaceship, length: 3, flags: TokenFlags(0x0) }
+            Token { kind: GreaterThanGreaterThan, length: 2, flags: TokenFlags(0x0) }
+        "#]],
+    );
+}
+
+#[test]
+fn digraphs() {
+    check_basic_tokens(
+        StdVersion::Cpp26,
+        "%:%:<:%><%:>%:%<::",
+        expect![[r#"
+            Token { kind: PoundPound, length: 4, flags: TokenFlags(DIGRAPH) }
+            Token { kind: OpenBracket, length: 2, flags: TokenFlags(DIGRAPH) }
+            Token { kind: CloseBrace, length: 2, flags: TokenFlags(DIGRAPH) }
+            Token { kind: OpenBrace, length: 2, flags: TokenFlags(DIGRAPH) }
+            Token { kind: CloseBracket, length: 2, flags: TokenFlags(DIGRAPH) }
+            Token { kind: Pound, length: 2, flags: TokenFlags(DIGRAPH) }
+            Token { kind: Percent, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: LessThan, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: ColonColon, length: 2, flags: TokenFlags(0x0) }
+        "#]],
+    );
+}
+
+#[test]
+fn digraphs_before_c95() {
+    check_basic_tokens(
+        StdVersion::C89,
+        "<:%:",
+        expect![[r#"
+        Token { kind: LessThan, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Colon, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Percent, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Colon, length: 1, flags: TokenFlags(0x0) }
+    "#]],
+    );
+}
+
+#[test]
+fn less_than_colon_colon() {
+    check_basic_tokens(
+        StdVersion::Cpp11,
+        "a<::b<:::c<::>",
+        expect![[r#"
+        Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: LessThan, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: ColonColon, length: 2, flags: TokenFlags(0x0) }
+        Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: OpenBracket, length: 2, flags: TokenFlags(DIGRAPH) }
+        Token { kind: ColonColon, length: 2, flags: TokenFlags(0x0) }
+        Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: OpenBracket, length: 2, flags: TokenFlags(DIGRAPH) }
+        Token { kind: CloseBracket, length: 2, flags: TokenFlags(DIGRAPH) }
+    "#]],
+    );
+    check_basic_tokens(
+        StdVersion::C17,
+        "a<::b",
+        expect![[r#"
+        Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: OpenBracket, length: 2, flags: TokenFlags(DIGRAPH) }
+        Token { kind: Colon, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+    "#]],
+    );
+}
+
+#[test]
+fn dollars_in_identifiers() {
+    let code = "$a a$1 L$";
+    let lang_opts = LangOptions::new(StdVersion::C17);
+    check_cursor_tokens(
+        Cursor::new(code, lang_opts.with_dollars_in_identifiers(true)),
+        code,
+        expect![[r#"
+            Token { kind: Identifier { has_univ_char: false }, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 3, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 2, flags: TokenFlags(0x0) }
+        "#]],
+        Cursor::next_token,
+    );
+    check_cursor_tokens(
+        Cursor::new(code, lang_opts),
+        code,
+        expect![[r#"
+            Token { kind: Unknown, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Unknown, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 0, has_long_suffix: false }), length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Unknown, length: 1, flags: TokenFlags(0x0) }
+        "#]],
+        Cursor::next_token,
+    );
+    let gnu17 = StdDialect::gnu(StdVersion::C17).unwrap();
+    assert!(Cursor::new(code, gnu17)
+        .lang_opts()
+        .has_dollars_in_identifiers());
+}
+
+#[test]
+fn digraphs_disabled() {
+    let code = "<: :> <% %> %: %:%:";
+    let lang_opts = LangOptions::new(StdVersion::Cpp26).with_digraphs(false);
+    check_cursor_tokens(
+        Cursor::new(code, lang_opts),
+        code,
+        expect![[r#"
+            Token { kind: LessThan, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Colon, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Colon, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: GreaterThan, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: LessThan, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Percent, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Percent, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: GreaterThan, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Percent, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Colon, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Percent, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Colon, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Percent, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Colon, length: 1, flags: TokenFlags(0x0) }
+        "#]],
+        Cursor::next_token,
+    );
+}
+
+#[test]
+fn trigraphs_enabled() {
+    check_trigraph_tokens(
+        StdVersion::Cpp14,
+        "??=define X a ??/\n??(??)??<??>??!??'??-??!??!??",
+        expect![[r#"
+            Token { kind: Pound, length: 3, flags: TokenFlags(TRIGRAPH) }
+            Token { kind: Identifier { has_univ_char: false }, length: 6, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 5, flags: TokenFlags(NEWLINE | TRIGRAPH) }
+            Token { kind: OpenBracket, length: 3, flags: TokenFlags(TRIGRAPH) }
+            Token { kind: CloseBracket, length: 3, flags: TokenFlags(TRIGRAPH) }
+            Token { kind: OpenBrace, length: 3, flags: TokenFlags(TRIGRAPH) }
+            Token { kind: CloseBrace, length: 3, flags: TokenFlags(TRIGRAPH) }
+            Token { kind: Pipe, length: 3, flags: TokenFlags(TRIGRAPH) }
+            Token { kind: Caret, length: 3, flags: TokenFlags(TRIGRAPH) }
+            Token { kind: Tilde, length: 3, flags: TokenFlags(TRIGRAPH) }
+            Token { kind: PipePipe, length: 6, flags: TokenFlags(TRIGRAPH) }
+            Token { kind: QuestionMark, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: QuestionMark, length: 1, flags: TokenFlags(0x0) }
+        "#]],
+    );
+}
+
+#[test]
+fn trigraphs_in_bulk_runs() {
+    check_trigraph_tokens(
+        StdVersion::C17,
+        "// a??/\nb\n/* ?\u{e9}??/ */ ab??=",
+        expect![[r#"
+            Token { kind: LineComment, length: 9, flags: TokenFlags(NEWLINE | TRIGRAPH) }
+            Token { kind: Whitespace { splits_lines: true }, length: 1, flags: TokenFlags(NEWLINE) }
+            Token { kind: BlockComment, length: 12, flags: TokenFlags(TRIGRAPH) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Identifier { has_univ_char: false }, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: Pound, length: 3, flags: TokenFlags(TRIGRAPH) }
+        "#]],
+    );
+}
+
+#[test]
+fn trigraphs_removed() {
+    check_trigraph_tokens(
+        StdVersion::Cpp17,
+        "??=??(",
+        expect![[r#"
+        Token { kind: QuestionMark, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: QuestionMark, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Equal, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: QuestionMark, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: QuestionMark, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: OpenParen, length: 1, flags: TokenFlags(0x0) }
+    "#]],
+    );
+    check_trigraph_tokens(
+        StdVersion::C23,
+        "??=??(",
+        expect![[r#"
+        Token { kind: QuestionMark, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: QuestionMark, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Equal, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: QuestionMark, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: QuestionMark, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: OpenParen, length: 1, flags: TokenFlags(0x0) }
+    "#]],
+    );
+}
+
+#[test]
+fn trigraphs_reverted_in_raw_string() {
+    check_trigraph_tokens(
+        StdVersion::Cpp11,
+        r#"R"(??)")"??="#,
+        expect![[r#"
+            Token { kind: RawStr { lit_type: Default, delim: DCharSeq { d_char: 0, count: 0 } }, length: 7, flags: TokenFlags(0x0) }
+            Token { kind: CloseParen, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Str { lit_type: Default, has_esc: false }, length: 4, flags: TokenFlags(UNTERMINATED | TRIGRAPH) }
+        "#]],
+    );
+}
+
+#[test]
+fn pp_number_floats() {
+    check_basic_tokens(
+        StdVersion::Cpp26,
+        "1.5e-3 .5 1. 1e10f 09.5 1..2 1e+x",
+        expect![[r#"
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: true, has_exponent: true, suffix_len: 0, has_long_suffix: false }), length: 6, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: true, has_exponent: false, suffix_len: 0, has_long_suffix: false }), length: 2, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: true, has_exponent: false, suffix_len: 0, has_long_suffix: false }), length: 2, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: true, has_exponent: true, suffix_len: 1, has_long_suffix: false }), length: 5, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: true, has_exponent: false, suffix_len: 0, has_long_suffix: false }), length: 4, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: true, has_exponent: false, suffix_len: 2, has_long_suffix: false }), length: 4, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 3, has_long_suffix: false }), length: 4, flags: TokenFlags(0x0) }
+        "#]],
+    );
+}
+
+#[test]
+fn pp_number_long_suffix() {
+    use clowncc_token::TokenKind;
+
+    let suffix_len = |code: &str| {
+        let token = Cursor::new(code, StdVersion::Cpp26).next_token().unwrap();
+        let TokenKind::Number(info) = token.kind() else {
+            panic!("expected a number, found {token:?}");
+        };
+        (info.suffix_len(), info.has_long_suffix())
+    };
+    let suffix = format!("_{}", "x".repeat(254));
+    assert_eq!(suffix_len(&format!("1{suffix}")), (255, false));
+    assert_eq!(suffix_len(&format!("1{suffix}x")), (255, true));
+}
+
+#[test]
+fn pp_number_hex_floats() {
+    check_basic_tokens(
+        StdVersion::Cpp17,
+        "0x1.8p3 0x.8p-1L 0x1e+1 0x1p",
+        expect![[r#"
+            Token { kind: Number(NumberInfo { base: Hexidecimal, has_sep: false, is_float: true, has_exponent: true, suffix_len: 0, has_long_suffix: false }), length: 7, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Hexidecimal, has_sep: false, is_float: true, has_exponent: true, suffix_len: 1, has_long_suffix: false }), length: 8, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Hexidecimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 2, has_long_suffix: false }), length: 6, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Hexidecimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 1, has_long_suffix: false }), length: 4, flags: TokenFlags(0x0) }
+        "#]],
+    );
+    check_basic_tokens(
+        StdVersion::Cpp14,
+        "0x1.8p-3",
+        expect![[r#"
+        Token { kind: Number(NumberInfo { base: Hexidecimal, has_sep: false, is_float: true, has_exponent: false, suffix_len: 1, has_long_suffix: false }), length: 6, flags: TokenFlags(0x0) }
+        Token { kind: Minus, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 0, has_long_suffix: false }), length: 1, flags: TokenFlags(0x0) }
+    "#]],
+    );
+}
+
+#[test]
+fn pp_number_suffixes() {
+    check_basic_tokens(
+        StdVersion::Cpp26,
+        "10ul 1'000.0f 123_km 017 0 0b101'1uz 0x 0xg",
+        expect![[r#"
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 2, has_long_suffix: false }), length: 4, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: true, is_float: true, has_exponent: false, suffix_len: 1, has_long_suffix: false }), length: 8, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 3, has_long_suffix: false }), length: 6, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Octal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 0, has_long_suffix: false }), length: 3, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 0, has_long_suffix: false }), length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Binary, has_sep: true, is_float: false, has_exponent: false, suffix_len: 2, has_long_suffix: false }), length: 9, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: StrayNumPrefix { base: Hexidecimal }, length: 2, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: StrayNumPrefix { base: Hexidecimal }, length: 3, flags: TokenFlags(0x0) }
+        "#]],
+    );
+}
+
+#[test]
+fn ud_suffixes() {
+    check_basic_tokens(
+        StdVersion::Cpp11,
+        r#""abc"_sv 'x'_c R"(a)"_r 42_ms "%"PRId64 "é"_é "a"+"#,
+        expect![[r#"
+            Token { kind: Str { lit_type: Default, has_esc: false }, length: 8, flags: TokenFlags(UD_SUFFIX) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: CharSeq { lit_type: Default, has_esc: false }, length: 5, flags: TokenFlags(UD_SUFFIX) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: RawStr { lit_type: Default, delim: DCharSeq { d_char: 0, count: 0 } }, length: 8, flags: TokenFlags(UD_SUFFIX) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Number(NumberInfo { base: Decimal, has_sep: false, is_float: false, has_exponent: false, suffix_len: 3, has_long_suffix: false }), length: 5, flags: TokenFlags(0x0) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Str { lit_type: Default, has_esc: false }, length: 9, flags: TokenFlags(UD_SUFFIX) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Str { lit_type: Default, has_esc: false }, length: 7, flags: TokenFlags(UD_SUFFIX) }
+            Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+            Token { kind: Str { lit_type: Default, has_esc: false }, length: 3, flags: TokenFlags(0x0) }
+            Token { kind: Plus, length: 1, flags: TokenFlags(0x0) }
+        "#]],
+    );
+}
+
+#[test]
+fn ud_suffixes_not_in_c() {
+    check_basic_tokens(
+        StdVersion::C23,
+        r#""abc"_sv 'x'_c"#,
+        expect![[r#"
+        Token { kind: Str { lit_type: Default, has_esc: false }, length: 5, flags: TokenFlags(0x0) }
+        Token { kind: Identifier { has_univ_char: false }, length: 3, flags: TokenFlags(0x0) }
+        Token { kind: Whitespace { splits_lines: false }, length: 1, flags: TokenFlags(0x0) }
+        Token { kind: CharSeq { lit_type: Default, has_esc: false }, length: 3, flags: TokenFlags(0x0) }
+        Token { kind: Identifier { has_univ_char: false }, length: 2, flags: TokenFlags(0x0) }
+    "#]],
+    );
+}
+
+fn ud_suffix_splits(code: &str) -> Vec<Option<(&str, &str)>> {
+    let mut cursor = Cursor::new(code, StdVersion::Cpp26);
+    let mut start = 0;
+    core::iter::from_fn(|| cursor.next_token())
+        .map(|t| {
+            let text = &code[start..start + t.length() as usize];
+            start += text.len();
+            t.ud_suffix_offset(text).map(|o| text.split_at(o))
+        })
+        .collect()
+}
+
+#[test]
+fn ud_suffix_offset() {
+    let splits = ud_suffix_splits;
+    assert_eq!(splits(r#""abc"_sv"#), [Some((r#""abc""#, "_sv"))]);
+    assert_eq!(splits("'\\''_c"), [Some(("'\\''", "_c"))]);
+    assert_eq!(splits(r#"R"x("y")x"_q"#), [Some((r#"R"x("y")x""#, "_q"))]);
+    assert_eq!(splits(r#""abc""#), [None]);
+}
+
+#[test]
+fn kind_classification() {
+    let code = "#include <a.h>\n// c\nx = 1 + u8'y' \"z\" ?? \\ @ 'w\nR\"(";
+    let mut cursor = Cursor::new(code, StdVersion::Cpp26);
+    let mut out = String::new();
+    let mut header = false;
+    let mut start = 0;
+    while let Some(token) = if header {
+        cursor.next_token_header()
+    } else {
+        cursor.next_token()
+    } {
+        let text = &code[start..start + token.length() as usize];
+        start += text.len();
+        let kind = token.kind();
+        if !kind.is_trivia() {
+            header = text == "include";
+        }
+        let classes = [
+            ("trivia", kind.is_trivia()),
+            ("literal", kind.is_literal()),
+            ("punctuator", kind.is_punctuator()),
+            ("pp-token kind", kind.is_pp_token() && !token.is_pp_token()),
+            ("pp-token", token.is_pp_token()),
+        ];
+        write!(out, "{text:?} {:?}", kind.spelling()).unwrap();
+        for (class, _) in classes.iter().filter(|(_, is)| *is) {
+            write!(out, " {class}").unwrap();
+        }
+        out.push('\n');
+    }
+    expect![[r##"
+        "#" Some("#") punctuator pp-token
+        "include" None pp-token
+        " " None trivia
+        "<a.h>" None pp-token
+        "\n" None trivia
+        "// c" None trivia
+        "\n" None trivia
+        "x" None pp-token
+        " " None trivia
+        "=" Some("=") punctuator pp-token
+        " " None trivia
+        "1" None literal pp-token
+        " " None trivia
+        "+" Some("+") punctuator pp-token
+        " " None trivia
+        "u8'y'" None literal pp-token
+        " " None trivia
+        "\"z\"" None literal pp-token
+        " " None trivia
+        "?" Some("?") punctuator pp-token
+        "?" Some("?") punctuator pp-token
+        " " None trivia
+        "\\" Some("\\")
+        " " None trivia
+        "@" None
+        " " None trivia
+        "'w\n" None literal pp-token kind
+        "R\"(" None
+    "##]]
+    .assert_eq(&out);
+}
@@ -1,9 +1,13 @@
 /// Generate enum with predicates, string accessors, and from string conversion.
+/// Each variant may list extra aliases which are also accepted by `from_str`,
+/// and `$what` names the enum in parse errors.
 // TODO: Move into its own support crate
 macro_rules! define_info_enum {
     (
         $(#[$attrs:meta])*
-        $enum_id:ident: $([$id_snake:ident, $name_str:expr]),* $(,)?
+        $enum_id:ident($what:expr): $([
+            $id_snake:ident, $name_str:expr $(, $alias:expr)*
+        ]),* $(,)?
     ) => {
         ::paste::paste! {
             $(#[$attrs])*
@@ -30,16 +34,61 @@ macro_rules! define_info_enum {
                 )*
             }
 
+            impl $enum_id {
+                /// Every name accepted by `from_str`.
+                const NAMES: &'static [&'static str] =
+                    &[$($name_str $(, $alias)*),*];
+                const PRIMARY_NAMES: &'static [&'static str] =
+                    &[$($name_str),*];
+            }
+
             impl ::core::str::FromStr for $enum_id {
-                type Err = ();
+                type Err = $crate::ParseError;
                 fn from_str(s: &str) -> Result<Self, Self::Err> {
                     match s {
-                        $($name_str => Ok(Self::[<$id_snake:camel>]),)*
-                        _ => Err(()),
+                        $($name_str $(| $alias)* => {
+                            Ok(Self::[<$id_snake:camel>])
+                        })*
+                        _ => Err($crate::ParseError::new(
+                            $what,
+                            s,
+                            Self::NAMES,
+                            Self::PRIMARY_NAMES,
+                        )),
                     }
                 }
             }
 
+            impl ::core::fmt::Display for $enum_id {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl ::serde::Serialize for $enum_id {
+                fn serialize<S: ::serde::Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(self.as_str())
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl<'de> ::serde::Deserialize<'de> for $enum_id {
+                fn deserialize<D: ::serde::Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Self, D::Error> {
+                    let visitor =
+                        $crate::serde_impl::FromStrVisitor::new($what);
+                    deserializer.deserialize_str(visitor)
+                }
+            }
+
         }
     };
 }
@@ -0,0 +1,108 @@
+use core::{fmt, str::FromStr};
+
+use crate::{ParseError, StdVersion};
+
+use strum::IntoEnumIterator;
+
+/// A language dialect as selected by `-std=`, which is an ISO version along
+/// with whether GNU extensions are enabled, i.e. `gnu11` is C11 with GNU
+/// extensions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StdDialect {
+    std_vers: StdVersion,
+    gnu_extensions: bool,
+}
+
+impl StdDialect {
+    /// The ISO dialect of a version without any extensions.
+    #[must_use]
+    pub const fn iso(std_vers: StdVersion) -> StdDialect {
+        StdDialect {
+            std_vers,
+            gnu_extensions: false,
+        }
+    }
+
+    /// The GNU dialect of a version, if compilers accept one.
+    #[must_use]
+    pub const fn gnu(std_vers: StdVersion) -> Option<StdDialect> {
+        if std_vers.gnu_names().is_empty() {
+            return None;
+        }
+        Some(StdDialect {
+            std_vers,
+            gnu_extensions: true,
+        })
+    }
+
+    #[must_use]
+    pub const fn std_version(self) -> StdVersion {
+        self.std_vers
+    }
+
+    #[must_use]
+    pub const fn has_gnu_extensions(self) -> bool {
+        self.gnu_extensions
+    }
+
+    /// Every dialect, ISO dialects first.
+    pub fn iter() -> impl Iterator<Item = StdDialect> {
+        let gnu = StdVersion::iter().filter_map(StdDialect::gnu);
+        StdVersion::iter().map(StdDialect::iso).chain(gnu)
+    }
+
+    /// The primary name of the dialect for `-std=`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self.std_vers.gnu_names() {
+            [gnu_name, ..] if self.gnu_extensions => gnu_name,
+            _ => self.std_vers.as_str(),
+        }
+    }
+}
+
+impl From<StdVersion> for StdDialect {
+    fn from(std_vers: StdVersion) -> StdDialect {
+        StdDialect::iso(std_vers)
+    }
+}
+
+impl FromStr for StdDialect {
+    type Err = ParseError;
+
+    /// Parses any name GCC or Clang accept for `-std=`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let std_vers: StdVersion = s.parse()?;
+        let gnu_extensions = std_vers.gnu_names().contains(&s);
+        Ok(StdDialect {
+            std_vers,
+            gnu_extensions,
+        })
+    }
+}
+
+impl fmt::Display for StdDialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for StdDialect {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StdDialect {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let visitor = crate::serde_impl::FromStrVisitor::new("dialect");
+        deserializer.deserialize_str(visitor)
+    }
+}
@@ -0,0 +1,202 @@
+use crate::StdVersion::{self, *};
+
+/// A feature which reports its value through a feature-test macro or
+/// `__has_cpp_attribute`, with the value it has from each version onwards.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Feature {
+    name: &'static str,
+    history: &'static [(StdVersion, u32)],
+}
+
+impl Feature {
+    const fn new(
+        name: &'static str,
+        history: &'static [(StdVersion, u32)],
+    ) -> Feature {
+        Feature { name, history }
+    }
+
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Each version which introduced or updated the feature along with the
+    /// value from that version onwards, oldest first.
+    #[must_use]
+    pub const fn history(&self) -> &'static [(StdVersion, u32)] {
+        self.history
+    }
+
+    /// Value of the feature in a version, if the version has it.
+    #[must_use]
+    pub fn value(&self, std_vers: StdVersion) -> Option<u32> {
+        self.history
+            .iter()
+            .rev()
+            .find(|&&(since, _)| std_vers.is_since(since))
+            .map(|&(_, value)| value)
+    }
+}
+
+/// The SD-6 feature-test macros of the language, sorted by name. Library
+/// macros belong to `<version>` so are not included.
+pub const FEATURE_TEST_MACROS: &[Feature] = &[
+    Feature::new("__cpp_aggregate_bases", &[(Cpp17, 201603)]),
+    Feature::new("__cpp_aggregate_nsdmi", &[(Cpp14, 201304)]),
+    Feature::new("__cpp_aggregate_paren_init", &[(Cpp20, 201902)]),
+    Feature::new("__cpp_alias_templates", &[(Cpp11, 200704)]),
+    Feature::new("__cpp_aligned_new", &[(Cpp17, 201606)]),
+    Feature::new("__cpp_attributes", &[(Cpp11, 200809)]),
+    Feature::new("__cpp_auto_cast", &[(Cpp23, 202110)]),
+    Feature::new("__cpp_binary_literals", &[(Cpp14, 201304)]),
+    Feature::new("__cpp_capture_star_this", &[(Cpp17, 201603)]),
+    Feature::new("__cpp_char8_t", &[(Cpp20, 201811), (Cpp23, 202207)]),
+    Feature::new("__cpp_concepts", &[(Cpp20, 202002)]),
+    Feature::new("__cpp_conditional_explicit", &[(Cpp20, 201806)]),
+    Feature::new("__cpp_consteval", &[(Cpp20, 201811), (Cpp23, 202211)]),
+    Feature::new(
+        "__cpp_constexpr",
+        &[
+            (Cpp11, 200704),
+            (Cpp14, 201304),
+            (Cpp17, 201603),
+            (Cpp20, 202002),
+            (Cpp23, 202211),
+            (Cpp26, 202406),
+        ],
+    ),
+    Feature::new("__cpp_constexpr_dynamic_alloc", &[(Cpp20, 201907)]),
+    Feature::new("__cpp_constexpr_exceptions", &[(Cpp26, 202411)]),
+    Feature::new("__cpp_constexpr_in_decltype", &[(Cpp20, 201711)]),
+    Feature::new("__cpp_constinit", &[(Cpp20, 201907)]),
+    Feature::new("__cpp_contracts", &[(Cpp26, 202502)]),
+    Feature::new("__cpp_decltype", &[(Cpp11, 200707)]),
+    Feature::new("__cpp_decltype_auto", &[(Cpp14, 201304)]),
+    Feature::new(
+        "__cpp_deduction_guides",
+        &[(Cpp17, 201703), (Cpp20, 201907)],
+    ),
+    Feature::new("__cpp_delegating_constructors", &[(Cpp11, 200604)]),
+    Feature::new("__cpp_deleted_function", &[(Cpp26, 202403)]),
+    Feature::new("__cpp_designated_initializers", &[(Cpp20, 201707)]),
+    Feature::new("__cpp_enumerator_attributes", &[(Cpp17, 201411)]),
+    Feature::new("__cpp_exceptions", &[(Cpp98, 199711)]),
+    Feature::new("__cpp_explicit_this_parameter", &[(Cpp23, 202110)]),
+    Feature::new("__cpp_fold_expressions", &[(Cpp17, 201603)]),
+    Feature::new("__cpp_generic_lambdas", &[(Cpp14, 201304), (Cpp20, 201707)]),
+    Feature::new("__cpp_guaranteed_copy_elision", &[(Cpp17, 201606)]),
+    Feature::new("__cpp_hex_float", &[(Cpp17, 201603)]),
+    Feature::new("__cpp_if_consteval", &[(Cpp23, 202106)]),
+    Feature::new("__cpp_if_constexpr", &[(Cpp17, 201606)]),
+    Feature::new("__cpp_impl_coroutine", &[(Cpp20, 201902)]),
+    Feature::new("__cpp_impl_destroying_delete", &[(Cpp20, 201806)]),
+    Feature::new("__cpp_impl_three_way_comparison", &[(Cpp20, 201907)]),
+    Feature::new("__cpp_implicit_move", &[(Cpp23, 202207)]),
+    Feature::new(
+        "__cpp_inheriting_constructors",
+        &[(Cpp11, 200802), (Cpp17, 201511)],
+    ),
+    Feature::new("__cpp_init_captures", &[(Cpp14, 201304), (Cpp20, 201803)]),
+    Feature::new("__cpp_initializer_lists", &[(Cpp11, 200806)]),
+    Feature::new("__cpp_inline_variables", &[(Cpp17, 201606)]),
+    Feature::new("__cpp_lambdas", &[(Cpp11, 200907)]),
+    Feature::new("__cpp_modules", &[(Cpp20, 201907)]),
+    Feature::new("__cpp_multidimensional_subscript", &[(Cpp23, 202211)]),
+    Feature::new("__cpp_named_character_escapes", &[(Cpp23, 202207)]),
+    Feature::new("__cpp_namespace_attributes", &[(Cpp17, 201411)]),
+    Feature::new("__cpp_noexcept_function_type", &[(Cpp17, 201510)]),
+    Feature::new(
+        "__cpp_nontype_template_args",
+        &[(Cpp17, 201411), (Cpp20, 201911)],
+    ),
+    Feature::new("__cpp_nontype_template_parameter_auto", &[(Cpp17, 201606)]),
+    Feature::new("__cpp_nsdmi", &[(Cpp11, 200809)]),
+    Feature::new("__cpp_pack_indexing", &[(Cpp26, 202311)]),
+    Feature::new("__cpp_placeholder_variables", &[(Cpp26, 202306)]),
+    Feature::new("__cpp_pp_embed", &[(Cpp26, 202502)]),
+    Feature::new(
+        "__cpp_range_based_for",
+        &[(Cpp11, 200907), (Cpp17, 201603), (Cpp23, 202211)],
+    ),
+    Feature::new("__cpp_raw_strings", &[(Cpp11, 200710)]),
+    Feature::new("__cpp_ref_qualifiers", &[(Cpp11, 200710)]),
+    Feature::new("__cpp_return_type_deduction", &[(Cpp14, 201304)]),
+    Feature::new("__cpp_rtti", &[(Cpp98, 199711)]),
+    Feature::new("__cpp_rvalue_references", &[(Cpp11, 200610)]),
+    Feature::new("__cpp_size_t_suffix", &[(Cpp23, 202011)]),
+    Feature::new("__cpp_sized_deallocation", &[(Cpp14, 201309)]),
+    Feature::new(
+        "__cpp_static_assert",
+        &[(Cpp11, 200410), (Cpp17, 201411), (Cpp26, 202306)],
+    ),
+    Feature::new("__cpp_static_call_operator", &[(Cpp23, 202207)]),
+    Feature::new(
+        "__cpp_structured_bindings",
+        &[(Cpp17, 201606), (Cpp26, 202411)],
+    ),
+    Feature::new("__cpp_template_template_args", &[(Cpp17, 201611)]),
+    Feature::new("__cpp_threadsafe_static_init", &[(Cpp11, 200806)]),
+    Feature::new("__cpp_unicode_characters", &[(Cpp11, 200704)]),
+    Feature::new("__cpp_unicode_literals", &[(Cpp11, 200710)]),
+    Feature::new("__cpp_user_defined_literals", &[(Cpp11, 200809)]),
+    Feature::new("__cpp_using_enum", &[(Cpp20, 201907)]),
+    Feature::new("__cpp_variable_templates", &[(Cpp14, 201304)]),
+    Feature::new("__cpp_variadic_friend", &[(Cpp26, 202403)]),
+    Feature::new("__cpp_variadic_templates", &[(Cpp11, 200704)]),
+    Feature::new("__cpp_variadic_using", &[(Cpp17, 201611)]),
+];
+
+/// The standard attributes, sorted by name, with the values reported by
+/// `__has_cpp_attribute` in C++ and `__has_c_attribute` in C.
+pub const ATTRIBUTES: &[Feature] = &[
+    Feature::new("_Noreturn", &[(C23, 202202)]),
+    Feature::new("assume", &[(Cpp23, 202207)]),
+    Feature::new("carries_dependency", &[(Cpp11, 200809)]),
+    Feature::new("deprecated", &[(C23, 201904), (Cpp14, 201309)]),
+    Feature::new("fallthrough", &[(C23, 201904), (Cpp17, 201603)]),
+    Feature::new("indeterminate", &[(Cpp26, 202403)]),
+    Feature::new("likely", &[(Cpp20, 201803)]),
+    Feature::new("maybe_unused", &[(C23, 201904), (Cpp17, 201603)]),
+    Feature::new("no_unique_address", &[(Cpp20, 201803)]),
+    Feature::new(
+        "nodiscard",
+        &[(C23, 202003), (Cpp17, 201603), (Cpp20, 201907)],
+    ),
+    Feature::new("noreturn", &[(C23, 202202), (Cpp11, 200809)]),
+    Feature::new("reproducible", &[(C23, 202207)]),
+    Feature::new("unlikely", &[(Cpp20, 201803)]),
+    Feature::new("unsequenced", &[(C23, 202207)]),
+];
+
+fn find(table: &'static [Feature], name: &str) -> Option<&'static Feature> {
+    let idx = table.binary_search_by(|f| f.name.cmp(name)).ok()?;
+    Some(&table[idx])
+}
+
+/// Finds an SD-6 feature-test macro by name, i.e. `__cpp_constexpr`.
+#[must_use]
+pub fn feature_test_macro(name: &str) -> Option<&'static Feature> {
+    find(FEATURE_TEST_MACROS, name)
+}
+
+/// Every feature-test macro defined in a version along with its value, to
+/// seed the predefined macros.
+pub fn feature_test_macros(
+    std_vers: StdVersion,
+) -> impl Iterator<Item = (&'static str, u32)> {
+    FEATURE_TEST_MACROS
+        .iter()
+        .filter_map(move |f| Some((f.name, f.value(std_vers)?)))
+}
+
+/// Finds a standard attribute by name, where `__name__` is the same as
+/// `name` as it is for `__has_cpp_attribute`.
+#[must_use]
+pub fn attribute(name: &str) -> Option<&'static Feature> {
+    let name = name
+        .strip_prefix("__")
+        .and_then(|name| name.strip_suffix("__"))
+        .unwrap_or(name);
+    find(ATTRIBUTES, name)
+}
@@ -0,0 +1,142 @@
+use crate::{StdDialect, StdVersion};
+
+/// A language version along with the switches for dialect behaviours, like
+/// `-fdollars-in-identifiers` or `-fno-digraphs`.
+///
+/// Every switch starts from the default for the version and dialect, and the
+/// ones which only exist in some versions stay off in the others.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LangOptions {
+    std_vers: StdVersion,
+    gnu_extensions: bool,
+    ms_extensions: bool,
+    dollars_in_identifiers: bool,
+    trigraphs: bool,
+    digraphs: bool,
+    char8_t: bool,
+}
+
+impl LangOptions {
+    /// The options of the ISO dialect of a version.
+    #[must_use]
+    pub const fn new(std_vers: StdVersion) -> LangOptions {
+        LangOptions::from_dialect(StdDialect::iso(std_vers))
+    }
+
+    /// The options of a dialect, where GNU dialects allow `$` in
+    /// identifiers.
+    #[must_use]
+    pub const fn from_dialect(dialect: StdDialect) -> LangOptions {
+        let std_vers = dialect.std_version();
+        LangOptions {
+            std_vers,
+            gnu_extensions: dialect.has_gnu_extensions(),
+            ms_extensions: false,
+            dollars_in_identifiers: dialect.has_gnu_extensions(),
+            trigraphs: false,
+            digraphs: std_vers.as_language().is_cpp()
+                || std_vers.is_since_c95(),
+            char8_t: std_vers.is_since_cpp20(),
+        }
+    }
+
+    /// `-fms-extensions`
+    #[must_use]
+    pub const fn with_ms_extensions(mut self, enabled: bool) -> LangOptions {
+        self.ms_extensions = enabled;
+        self
+    }
+
+    /// `-fdollars-in-identifiers`
+    #[must_use]
+    pub const fn with_dollars_in_identifiers(
+        mut self,
+        enabled: bool,
+    ) -> LangOptions {
+        self.dollars_in_identifiers = enabled;
+        self
+    }
+
+    /// `-ftrigraphs`, which opts into trigraph replacement (translation
+    /// phase 1). Trigraphs were removed in C++17 and C23 so this has no
+    /// effect for those versions.
+    #[must_use]
+    pub const fn with_trigraphs(mut self, enabled: bool) -> LangOptions {
+        self.trigraphs = enabled;
+        self
+    }
+
+    /// `-fdigraphs`, which is on by default for the versions that have them.
+    #[must_use]
+    pub const fn with_digraphs(mut self, enabled: bool) -> LangOptions {
+        self.digraphs = enabled;
+        self
+    }
+
+    /// `-fchar8_t`, which only has an effect for C++.
+    #[must_use]
+    pub const fn with_char8_t(mut self, enabled: bool) -> LangOptions {
+        self.char8_t = enabled;
+        self
+    }
+
+    #[must_use]
+    pub const fn std_version(&self) -> StdVersion {
+        self.std_vers
+    }
+
+    #[must_use]
+    pub const fn dialect(&self) -> StdDialect {
+        match StdDialect::gnu(self.std_vers) {
+            Some(gnu) if self.gnu_extensions => gnu,
+            _ => StdDialect::iso(self.std_vers),
+        }
+    }
+
+    #[must_use]
+    pub const fn has_gnu_extensions(&self) -> bool {
+        self.gnu_extensions
+    }
+
+    #[must_use]
+    pub const fn has_ms_extensions(&self) -> bool {
+        self.ms_extensions
+    }
+
+    #[must_use]
+    pub const fn has_dollars_in_identifiers(&self) -> bool {
+        self.dollars_in_identifiers
+    }
+
+    /// Whether trigraphs are replaced, which requires a version before C++17
+    /// or C23.
+    #[must_use]
+    pub const fn has_trigraphs(&self) -> bool {
+        self.trigraphs
+            && (self.std_vers.is_before_cpp17()
+                || self.std_vers.is_before_c23())
+    }
+
+    #[must_use]
+    pub const fn has_digraphs(&self) -> bool {
+        self.digraphs
+    }
+
+    /// Whether `char8_t` is a keyword and the type of `u8` literals.
+    #[must_use]
+    pub const fn has_char8_t(&self) -> bool {
+        self.char8_t && self.std_vers.as_language().is_cpp()
+    }
+}
+
+impl From<StdVersion> for LangOptions {
+    fn from(std_vers: StdVersion) -> LangOptions {
+        LangOptions::new(std_vers)
+    }
+}
+
+impl From<StdDialect> for LangOptions {
+    fn from(dialect: StdDialect) -> LangOptions {
+        LangOptions::from_dialect(dialect)
+    }
+}
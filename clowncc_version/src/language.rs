@@ -1,7 +1,7 @@
 macro_rules! implement {
     ($([$id_snake:ident, $name_str:expr]),* $(,)?) => {
         $crate::common_macros::define_info_enum!{
-            Language: $([$id_snake, $name_str]),*,
+            Language("language"): $([$id_snake, $name_str]),*,
         }
     }
 }
@@ -1,8 +1,24 @@
 #![no_std]
 
 mod common_macros;
+mod dialect;
+mod features;
+mod lang_options;
 mod language;
+mod parse_error;
+#[cfg(feature = "serde")]
+mod serde_impl;
 mod std_version;
+mod target;
+
+pub use dialect::StdDialect;
+pub use features::{
+    attribute, feature_test_macro, feature_test_macros, Feature, ATTRIBUTES,
+    FEATURE_TEST_MACROS,
+};
+pub use lang_options::LangOptions;
+pub use parse_error::ParseError;
+pub use target::{Arch, DataModel, Endian, Os, TargetInfo};
 
 use strum_macros::EnumIter;
 
@@ -15,25 +31,60 @@ language::implement! {
 }
 
 std_version::implement! {
+    // [language, version, name, [ISO aliases], [GNU dialect names]]
+
     // c
-    [C, c89, "c89"],
-    [C, c95, "c95"],
-    [C, c99, "c99"],
-    [C, c11, "c11"],
-    [C, c17, "c17"],
-    [C, c23, "c23"],
+    [C, c89, "c89", ["c90", "iso9899:1990"], ["gnu89", "gnu90"]],
+    [C, c95, "c95", ["iso9899:199409"], []],
+    [
+        C, c99, "c99",
+        ["c9x", "iso9899:1999", "iso9899:199x"],
+        ["gnu99", "gnu9x"],
+    ],
+    [C, c11, "c11", ["c1x", "iso9899:2011"], ["gnu11", "gnu1x"]],
+    [
+        C, c17, "c17",
+        ["c18", "iso9899:2017", "iso9899:2018"],
+        ["gnu17", "gnu18"],
+    ],
+    [C, c23, "c23", ["c2x", "iso9899:2024"], ["gnu23", "gnu2x"]],
+    [C, c2y, "c2y", [], ["gnu2y"]],
 
     // c++
-    [Cpp, cpp11, "c++11"],
-    [Cpp, cpp14, "c++14"],
-    [Cpp, cpp17, "c++17"],
-    [Cpp, cpp20, "c++20"],
-    [Cpp, cpp23, "c++23"],
-    [Cpp, cpp26, "c++26"],
+    [Cpp, cpp98, "c++98", ["c++03"], ["gnu++98", "gnu++03"]],
+    [Cpp, cpp11, "c++11", ["c++0x"], ["gnu++11", "gnu++0x"]],
+    [Cpp, cpp14, "c++14", ["c++1y"], ["gnu++14", "gnu++1y"]],
+    [Cpp, cpp17, "c++17", ["c++1z"], ["gnu++17", "gnu++1z"]],
+    [Cpp, cpp20, "c++20", ["c++2a"], ["gnu++20", "gnu++2a"]],
+    [Cpp, cpp23, "c++23", ["c++2b"], ["gnu++23", "gnu++2b"]],
+    [Cpp, cpp26, "c++26", ["c++2c"], ["gnu++26", "gnu++2c"]],
 }
 
 pub trait StdVersionSupported {
     fn is_in_std_version(&self, sv: StdVersion) -> bool;
+
+    /// The earliest version of the language which has this.
+    fn first_version(&self, lang: Language) -> Option<StdVersion> {
+        self.supported_versions()
+            .find(|sv| sv.as_language() == lang)
+    }
+
+    /// The latest version of the language which has this.
+    fn last_version(&self, lang: Language) -> Option<StdVersion> {
+        StdVersion::ALL
+            .iter()
+            .rev()
+            .copied()
+            .find(|&sv| sv.as_language() == lang && self.is_in_std_version(sv))
+    }
+
+    /// Every version which has this, oldest first within each language.
+    fn supported_versions(&self) -> impl Iterator<Item = StdVersion> + '_ {
+        StdVersion::ALL
+            .iter()
+            .copied()
+            .filter(move |&sv| self.is_in_std_version(sv))
+    }
 }
 
 pub trait LanguageSupported {
@@ -0,0 +1,149 @@
+use core::{cmp::Reverse, fmt};
+
+/// Longest input kept by [`ParseError`], where longer inputs are cut at a
+/// char boundary.
+const MAX_INPUT_LEN: usize = 32;
+
+/// Error from parsing a name which is not one of the valid choices, along
+/// with the closest valid name when one is similar enough to be a typo.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct ParseError {
+    what: &'static str,
+    input: [u8; MAX_INPUT_LEN],
+    input_len: u8,
+    is_truncated: bool,
+    choices: &'static [&'static str],
+    suggestion: Option<&'static str>,
+}
+
+impl ParseError {
+    /// Creates an error for `input` where `names` are all of the names which
+    /// are accepted and `choices` are the ones to list in the message.
+    pub(crate) fn new(
+        what: &'static str,
+        input: &str,
+        names: &'static [&'static str],
+        choices: &'static [&'static str],
+    ) -> ParseError {
+        let mut input_len = input.len().min(MAX_INPUT_LEN);
+        while !input.is_char_boundary(input_len) {
+            input_len -= 1;
+        }
+        let mut kept = [0; MAX_INPUT_LEN];
+        kept[..input_len].copy_from_slice(&input.as_bytes()[..input_len]);
+        ParseError {
+            what,
+            input: kept,
+            input_len: input_len as u8,
+            is_truncated: input_len < input.len(),
+            choices,
+            suggestion: closest_name(input, names),
+        }
+    }
+
+    /// What was being parsed, i.e. `standard version`.
+    #[must_use]
+    pub const fn what(&self) -> &'static str {
+        self.what
+    }
+
+    /// The input which failed to parse, cut to at most its first 32 bytes.
+    #[must_use]
+    pub fn input(&self) -> &str {
+        let input = &self.input[..usize::from(self.input_len)];
+        core::str::from_utf8(input).unwrap_or_default()
+    }
+
+    /// The primary name of every valid choice.
+    #[must_use]
+    pub const fn choices(&self) -> &'static [&'static str] {
+        self.choices
+    }
+
+    /// The valid name closest to the input, if any is close.
+    #[must_use]
+    pub const fn suggestion(&self) -> Option<&'static str> {
+        self.suggestion
+    }
+}
+
+impl fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseError")
+            .field("what", &self.what)
+            .field("input", &self.input())
+            .field("is_truncated", &self.is_truncated)
+            .field("choices", &self.choices)
+            .field("suggestion", &self.suggestion)
+            .finish()
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ellipsis = if self.is_truncated { "..." } else { "" };
+        let input = self.input().escape_debug();
+        write!(f, "unknown {} '{input}{ellipsis}';", self.what)?;
+        if let Some(suggestion) = self.suggestion {
+            write!(f, " did you mean '{suggestion}'?")?;
+        }
+        f.write_str(" expected one of:")?;
+        for (idx, choice) in self.choices.iter().enumerate() {
+            let sep = if idx == 0 { " " } else { ", " };
+            write!(f, "{sep}'{choice}'")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Longest name which the edit distance is computed for. Every name of a
+/// language or version fits.
+const MAX_NAME_LEN: usize = 24;
+
+/// Finds the name with the smallest edit distance to the input, ignoring
+/// case, as long as it takes at most two edits and the input is not a
+/// complete replacement. Ties go to the longest common prefix and then the
+/// earliest name, so `c++27` suggests `c++20` rather than `c++17`.
+fn closest_name(
+    input: &str,
+    names: &'static [&'static str],
+) -> Option<&'static str> {
+    names
+        .iter()
+        .map(|&name| (edit_distance(input, name), name))
+        .filter(|&(dist, name)| dist <= 2 && dist < name.len())
+        .min_by_key(|&(dist, name)| (dist, Reverse(common_prefix(input, name))))
+        .map(|(_, name)| name)
+}
+
+fn common_prefix(input: &str, name: &str) -> usize {
+    input
+        .bytes()
+        .zip(name.bytes())
+        .take_while(|(i, n)| i.eq_ignore_ascii_case(n))
+        .count()
+}
+
+/// Levenshtein distance between the input and an ASCII name.
+fn edit_distance(input: &str, name: &str) -> usize {
+    debug_assert!(name.is_ascii() && name.len() < MAX_NAME_LEN);
+    let name = name.as_bytes();
+    // Distances from the input so far to each prefix of the name
+    let mut row = [0; MAX_NAME_LEN];
+    for (idx, dist) in row.iter_mut().enumerate().take(name.len() + 1) {
+        *dist = idx;
+    }
+    for (idx, c) in input.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = idx + 1;
+        for (jdx, &n) in name.iter().enumerate() {
+            let substitution =
+                diagonal + usize::from(!c.eq_ignore_ascii_case(&char::from(n)));
+            diagonal = row[jdx + 1];
+            row[jdx + 1] = substitution.min(row[jdx] + 1).min(diagonal + 1);
+        }
+    }
+    row[name.len()]
+}
@@ -0,0 +1,34 @@
+use core::{fmt, marker::PhantomData, str::FromStr};
+
+use serde::de;
+
+/// Deserializes a value from any of the names its `FromStr` accepts.
+pub(crate) struct FromStrVisitor<T> {
+    what: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T> FromStrVisitor<T> {
+    pub(crate) const fn new(what: &'static str) -> FromStrVisitor<T> {
+        FromStrVisitor {
+            what,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> de::Visitor<'_> for FromStrVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a {} name", self.what)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<T, E> {
+        s.parse().map_err(E::custom)
+    }
+}
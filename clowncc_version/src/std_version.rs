@@ -1,14 +1,26 @@
-use crate::StdVersion;
+use crate::{Language, StdVersion};
 
 macro_rules! implement {
-    ($([$lang:ident, $id_snake:ident, $name_str:expr]),* $(,)?) => {
+    ($([
+        $lang:ident,
+        $id_snake:ident,
+        $name_str:expr,
+        [$($iso_alias:expr),* $(,)?],
+        [$($gnu_name:expr),* $(,)?] $(,)?
+    ]),* $(,)?) => {
         ::paste::paste! {
             $crate::common_macros::define_info_enum!{
                 #[derive(Ord, PartialOrd)]
-                StdVersion: $([$id_snake, $name_str]),*,
+                StdVersion("standard version"): $([
+                    $id_snake, $name_str $(, $iso_alias)* $(, $gnu_name)*
+                ]),*,
             }
 
             impl StdVersion {
+                /// Every version, oldest first within each language.
+                pub const ALL: &'static [StdVersion] =
+                    &[$(Self::[<$id_snake:camel>]),*];
+
                 #[must_use]
                 pub const fn as_language(self) -> Language {
                     match self {
@@ -16,14 +28,33 @@ macro_rules! implement {
                     }
                 }
 
+                /// Other names of the version accepted for `-std=`, i.e.
+                /// `c9x` and `iso9899:1999` for `c99`.
+                #[must_use]
+                pub const fn aliases(self) -> &'static [&'static str] {
+                    match self {
+                        $(Self::[<$id_snake:camel>] => &[$($iso_alias),*]),*
+                    }
+                }
+
+                /// Names of the version with GNU extensions accepted for
+                /// `-std=`, the first being the primary one, i.e. `gnu99`
+                /// and `gnu9x` for `c99`. Some versions have none.
+                #[must_use]
+                pub const fn gnu_names(self) -> &'static [&'static str] {
+                    match self {
+                        $(Self::[<$id_snake:camel>] => &[$($gnu_name),*]),*
+                    }
+                }
+
                 $(
                     #[must_use]
-                    pub fn [<is_since_ $id_snake>](self) -> bool {
+                    pub const fn [<is_since_ $id_snake>](self) -> bool {
                         self.is_since(Self::[<$id_snake:camel>])
                     }
 
                     #[must_use]
-                    pub fn [<is_before_ $id_snake>](self) -> bool {
+                    pub const fn [<is_before_ $id_snake>](self) -> bool {
                         self.is_before(Self::[<$id_snake:camel>])
                     }
                 )*
@@ -31,7 +62,6 @@ macro_rules! implement {
 
             impl ::core::ops::Deref for StdVersion {
                 type Target = Language;
-                #[must_use]
                 fn deref(&self) -> &Self::Target {
                     match self {
                         $(Self::[<$id_snake:camel>] => &Language::$lang),*
@@ -47,15 +77,55 @@ impl StdVersion {
     pub const C_DEFAULT_VERSION: StdVersion = StdVersion::C17;
     pub const CPP_DEFAULT_VERSION: StdVersion = StdVersion::Cpp17;
     pub const C_EARLIEST_VERSION: StdVersion = StdVersion::C89;
-    pub const CPP_EARLIEST_VERSION: StdVersion = StdVersion::Cpp11;
+    pub const CPP_EARLIEST_VERSION: StdVersion = StdVersion::Cpp98;
+
+    #[must_use]
+    pub const fn is_since(self, since: StdVersion) -> bool {
+        self.is_same_language(since) && since as u8 <= self as u8
+    }
+
+    #[must_use]
+    pub const fn is_before(self, since: StdVersion) -> bool {
+        self.is_same_language(since) && since as u8 > self as u8
+    }
 
+    /// Comparison of the languages which works in const contexts, unlike
+    /// `PartialEq`.
+    const fn is_same_language(self, other: StdVersion) -> bool {
+        self.as_language() as u8 == other.as_language() as u8
+    }
+
+    /// Name of the predefined macro holding the version, which is
+    /// `__STDC_VERSION__` for C and `__cplusplus` for C++.
     #[must_use]
-    pub fn is_since(self, since: StdVersion) -> bool {
-        since.as_language() == self.as_language() && since <= self
+    pub const fn version_macro(self) -> &'static str {
+        match self.as_language() {
+            Language::C => "__STDC_VERSION__",
+            Language::Cpp => "__cplusplus",
+        }
     }
 
+    /// Value of [`StdVersion::version_macro`], i.e. `201112` for C11. C89
+    /// predates `__STDC_VERSION__` so has none, and drafts use the value
+    /// compilers currently give them.
     #[must_use]
-    pub fn is_before(self, since: StdVersion) -> bool {
-        since.as_language() == self.as_language() && since > self
+    pub const fn version_value(self) -> Option<u32> {
+        let value = match self {
+            StdVersion::C89 => return None,
+            StdVersion::C95 => 199409,
+            StdVersion::C99 => 199901,
+            StdVersion::C11 => 201112,
+            StdVersion::C17 => 201710,
+            StdVersion::C23 => 202311,
+            StdVersion::C2y => 202400,
+            StdVersion::Cpp98 => 199711,
+            StdVersion::Cpp11 => 201103,
+            StdVersion::Cpp14 => 201402,
+            StdVersion::Cpp17 => 201703,
+            StdVersion::Cpp20 => 202002,
+            StdVersion::Cpp23 => 202302,
+            StdVersion::Cpp26 => 202400,
+        };
+        Some(value)
     }
 }
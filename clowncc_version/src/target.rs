@@ -0,0 +1,310 @@
+use core::{fmt, str::FromStr};
+
+use crate::ParseError;
+
+/// Processor architecture of a target.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Arch {
+    X86,
+    X86_64,
+    Arm,
+    Aarch64,
+    Riscv64,
+}
+
+impl Arch {
+    const NAMES: &'static [&'static str] = &[
+        "i386",
+        "i486",
+        "i586",
+        "i686",
+        "x86",
+        "x86_64",
+        "amd64",
+        "arm",
+        "armeb",
+        "thumb",
+        "thumbeb",
+        "aarch64",
+        "aarch64_be",
+        "arm64",
+        "riscv64",
+        "riscv64gc",
+    ];
+    const PRIMARY_NAMES: &'static [&'static str] =
+        &["i686", "x86_64", "arm", "aarch64", "riscv64"];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Arch::X86 => "i686",
+            Arch::X86_64 => "x86_64",
+            Arch::Arm => "arm",
+            Arch::Aarch64 => "aarch64",
+            Arch::Riscv64 => "riscv64",
+        }
+    }
+
+    #[must_use]
+    pub const fn is_64_bit(self) -> bool {
+        matches!(self, Arch::X86_64 | Arch::Aarch64 | Arch::Riscv64)
+    }
+
+    /// Parses the architecture of a triple along with its byte order, where
+    /// sub-architectures like `armv7em` or `thumbv6m` are the same as `arm`.
+    fn parse(s: &str) -> Result<(Arch, Endian), ParseError> {
+        let little = |arch| Ok((arch, Endian::Little));
+        let big = |arch| Ok((arch, Endian::Big));
+        match s {
+            "i386" | "i486" | "i586" | "i686" | "x86" => little(Arch::X86),
+            "x86_64" | "amd64" => little(Arch::X86_64),
+            "aarch64" | "arm64" => little(Arch::Aarch64),
+            "aarch64_be" => big(Arch::Aarch64),
+            "riscv64" | "riscv64gc" => little(Arch::Riscv64),
+            _ if s.starts_with("armeb") || s.starts_with("thumbeb") => {
+                big(Arch::Arm)
+            }
+            _ if s.starts_with("arm") || s.starts_with("thumb") => {
+                little(Arch::Arm)
+            }
+            _ => Err(ParseError::new(
+                "architecture",
+                s,
+                Arch::NAMES,
+                Arch::PRIMARY_NAMES,
+            )),
+        }
+    }
+}
+
+/// Operating system of a target, which is `none` for bare metal targets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Os {
+    Linux,
+    Windows,
+    Darwin,
+    BareMetal,
+}
+
+impl Os {
+    const NAMES: &'static [&'static str] = &[
+        "linux", "windows", "win32", "mingw32", "darwin", "macos", "macosx",
+        "none", "elf", "eabi", "eabihf",
+    ];
+    const PRIMARY_NAMES: &'static [&'static str] =
+        &["linux", "windows", "darwin", "none"];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Os::Linux => "linux",
+            Os::Windows => "windows",
+            Os::Darwin => "darwin",
+            Os::BareMetal => "none",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Os> {
+        match s {
+            "linux" => Some(Os::Linux),
+            "windows" | "win32" | "mingw32" => Some(Os::Windows),
+            "darwin" | "macos" | "macosx" => Some(Os::Darwin),
+            "none" | "elf" | "eabi" | "eabihf" => Some(Os::BareMetal),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+/// Widths of `int`, `long` and pointers, which is all that differs between
+/// the data models of the supported targets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DataModel {
+    /// 32-bit `int`, `long` and pointers.
+    Ilp32,
+    /// 64-bit `long` and pointers, as used by 64-bit Unix.
+    Lp64,
+    /// 64-bit `long long` and pointers but 32-bit `long`, as used by 64-bit
+    /// Windows.
+    Llp64,
+}
+
+impl DataModel {
+    #[must_use]
+    pub const fn int_width(self) -> u32 {
+        32
+    }
+
+    #[must_use]
+    pub const fn long_width(self) -> u32 {
+        match self {
+            DataModel::Ilp32 | DataModel::Llp64 => 32,
+            DataModel::Lp64 => 64,
+        }
+    }
+
+    #[must_use]
+    pub const fn long_long_width(self) -> u32 {
+        64
+    }
+
+    #[must_use]
+    pub const fn pointer_width(self) -> u32 {
+        match self {
+            DataModel::Ilp32 => 32,
+            DataModel::Lp64 | DataModel::Llp64 => 64,
+        }
+    }
+}
+
+/// The properties of a target which affect how source is interpreted, like
+/// the type of `wchar_t` for wide literals and the widths for predefined
+/// macros and layout.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TargetInfo {
+    arch: Arch,
+    os: Os,
+    endian: Endian,
+    data_model: DataModel,
+    char_signed: bool,
+    wchar_width: u32,
+    wchar_signed: bool,
+}
+
+impl TargetInfo {
+    pub const X86_64_LINUX_GNU: TargetInfo =
+        TargetInfo::new(Arch::X86_64, Os::Linux, Endian::Little);
+    pub const AARCH64_LINUX_GNU: TargetInfo =
+        TargetInfo::new(Arch::Aarch64, Os::Linux, Endian::Little);
+    pub const I686_LINUX_GNU: TargetInfo =
+        TargetInfo::new(Arch::X86, Os::Linux, Endian::Little);
+    pub const RISCV64_LINUX_GNU: TargetInfo =
+        TargetInfo::new(Arch::Riscv64, Os::Linux, Endian::Little);
+    pub const ARM_NONE_EABI: TargetInfo =
+        TargetInfo::new(Arch::Arm, Os::BareMetal, Endian::Little);
+
+    /// Creates a target following the ABI conventions of the architecture on
+    /// the operating system.
+    #[must_use]
+    pub const fn new(arch: Arch, os: Os, endian: Endian) -> TargetInfo {
+        let data_model = match (arch.is_64_bit(), os) {
+            (false, _) => DataModel::Ilp32,
+            (true, Os::Windows) => DataModel::Llp64,
+            (true, _) => DataModel::Lp64,
+        };
+        // Only x86 and Apple and Microsoft platforms keep `char` signed
+        let char_signed = matches!(arch, Arch::X86 | Arch::X86_64)
+            || matches!(os, Os::Darwin | Os::Windows);
+        let (wchar_width, wchar_signed) = match (arch, os) {
+            (_, Os::Windows) => (16, false),
+            (_, Os::Darwin) | (Arch::X86 | Arch::X86_64 | Arch::Riscv64, _) => {
+                (32, true)
+            }
+            (Arch::Arm | Arch::Aarch64, _) => (32, false),
+        };
+        TargetInfo {
+            arch,
+            os,
+            endian,
+            data_model,
+            char_signed,
+            wchar_width,
+            wchar_signed,
+        }
+    }
+
+    #[must_use]
+    pub const fn arch(&self) -> Arch {
+        self.arch
+    }
+
+    #[must_use]
+    pub const fn os(&self) -> Os {
+        self.os
+    }
+
+    #[must_use]
+    pub const fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    #[must_use]
+    pub const fn data_model(&self) -> DataModel {
+        self.data_model
+    }
+
+    /// Whether plain `char` is signed.
+    #[must_use]
+    pub const fn is_char_signed(&self) -> bool {
+        self.char_signed
+    }
+
+    #[must_use]
+    pub const fn wchar_width(&self) -> u32 {
+        self.wchar_width
+    }
+
+    #[must_use]
+    pub const fn is_wchar_signed(&self) -> bool {
+        self.wchar_signed
+    }
+
+    #[must_use]
+    pub const fn long_width(&self) -> u32 {
+        self.data_model.long_width()
+    }
+
+    #[must_use]
+    pub const fn pointer_width(&self) -> u32 {
+        self.data_model.pointer_width()
+    }
+}
+
+impl Default for TargetInfo {
+    fn default() -> TargetInfo {
+        TargetInfo::X86_64_LINUX_GNU
+    }
+}
+
+impl FromStr for TargetInfo {
+    type Err = ParseError;
+
+    /// Parses a target triple like `x86_64-unknown-linux-gnu`, where the
+    /// vendor and environment are optional and ignored as in
+    /// `aarch64-linux-gnu` and `arm-none-eabi`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('-');
+        let arch = parts.next().unwrap_or_default();
+        let (arch, endian) = Arch::parse(arch)?;
+        let rest: [_; 3] = core::array::from_fn(|_| parts.next());
+        let os = rest.iter().flatten().find_map(|part| Os::parse(part));
+        let Some(os) = os else {
+            // Blame the part in the usual position of the operating system
+            let part = rest[1].or(rest[0]).unwrap_or_default();
+            return Err(ParseError::new(
+                "operating system",
+                part,
+                Os::NAMES,
+                Os::PRIMARY_NAMES,
+            ));
+        };
+        Ok(TargetInfo::new(arch, os, endian))
+    }
+}
+
+impl fmt::Display for Arch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for Os {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
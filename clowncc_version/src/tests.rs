@@ -1,8 +1,17 @@
-use crate::{Language, StdVersion, StdVersion::*};
+use crate::{
+    attribute, feature_test_macro, feature_test_macros, Arch, DataModel,
+    Endian, LangOptions, Language, Os, StdDialect, StdVersion, StdVersion::*,
+    TargetInfo, ATTRIBUTES, FEATURE_TEST_MACROS,
+};
 
-const C_VERSIONS: [StdVersion; 6] = [C89, C95, C99, C11, C17, C23];
-const CPP_VERSIONS: [StdVersion; 6] =
-    [Cpp11, Cpp14, Cpp17, Cpp20, Cpp23, Cpp26];
+use strum::IntoEnumIterator;
+
+extern crate alloc;
+use alloc::{format, string::ToString};
+
+const C_VERSIONS: [StdVersion; 7] = [C89, C95, C99, C11, C17, C23, C2y];
+const CPP_VERSIONS: [StdVersion; 7] =
+    [Cpp98, Cpp11, Cpp14, Cpp17, Cpp20, Cpp23, Cpp26];
 
 #[test]
 fn std_version_deref_to_lang() {
@@ -16,6 +25,16 @@ fn std_version_deref_to_lang() {
     }
 }
 
+#[test]
+fn std_version_all() {
+    assert!(StdVersion::iter().eq(StdVersion::ALL.iter().copied()));
+
+    const {
+        assert!(Cpp20.is_since_cpp11() && !C23.is_since_cpp11());
+        assert!(C99.is_before_c11() && !Cpp98.is_before_c11());
+    }
+}
+
 macro_rules! assert_if {
     ($cond:expr, $pred:expr) => {
         assert!(if $cond { $pred } else { !$pred })
@@ -31,7 +50,9 @@ fn std_version_before() {
         assert_if!(3 > idx, sv.is_before_c11());
         assert_if!(4 > idx, sv.is_before_c17());
         assert_if!(5 > idx, sv.is_before_c23());
+        assert_if!(6 > idx, sv.is_before_c2y());
 
+        assert!(!sv.is_before_cpp98());
         assert!(!sv.is_before_cpp11());
         assert!(!sv.is_before_cpp14());
         assert!(!sv.is_before_cpp17());
@@ -41,12 +62,13 @@ fn std_version_before() {
     }
 
     for (idx, sv) in CPP_VERSIONS.into_iter().enumerate() {
-        assert!(!sv.is_before_cpp11());
-        assert_if!(1 > idx, sv.is_before_cpp14());
-        assert_if!(2 > idx, sv.is_before_cpp17());
-        assert_if!(3 > idx, sv.is_before_cpp20());
-        assert_if!(4 > idx, sv.is_before_cpp23());
-        assert_if!(5 > idx, sv.is_before_cpp26());
+        assert!(!sv.is_before_cpp98());
+        assert_if!(1 > idx, sv.is_before_cpp11());
+        assert_if!(2 > idx, sv.is_before_cpp14());
+        assert_if!(3 > idx, sv.is_before_cpp17());
+        assert_if!(4 > idx, sv.is_before_cpp20());
+        assert_if!(5 > idx, sv.is_before_cpp23());
+        assert_if!(6 > idx, sv.is_before_cpp26());
 
         assert!(!sv.is_before_c89());
         assert!(!sv.is_before_c95());
@@ -54,6 +76,7 @@ fn std_version_before() {
         assert!(!sv.is_before_c11());
         assert!(!sv.is_before_c17());
         assert!(!sv.is_before_c23());
+        assert!(!sv.is_before_c2y());
     }
 }
 
@@ -66,7 +89,9 @@ fn std_version_since() {
         assert_if!(3 <= idx, sv.is_since_c11());
         assert_if!(4 <= idx, sv.is_since_c17());
         assert_if!(5 <= idx, sv.is_since_c23());
+        assert_if!(6 <= idx, sv.is_since_c2y());
 
+        assert!(!sv.is_since_cpp98());
         assert!(!sv.is_since_cpp11());
         assert!(!sv.is_since_cpp14());
         assert!(!sv.is_since_cpp17());
@@ -76,12 +101,13 @@ fn std_version_since() {
     }
 
     for (idx, sv) in CPP_VERSIONS.into_iter().enumerate() {
-        assert!(sv.is_since_cpp11());
-        assert_if!(1 <= idx, sv.is_since_cpp14());
-        assert_if!(2 <= idx, sv.is_since_cpp17());
-        assert_if!(3 <= idx, sv.is_since_cpp20());
-        assert_if!(4 <= idx, sv.is_since_cpp23());
-        assert_if!(5 <= idx, sv.is_since_cpp26());
+        assert!(sv.is_since_cpp98());
+        assert_if!(1 <= idx, sv.is_since_cpp11());
+        assert_if!(2 <= idx, sv.is_since_cpp14());
+        assert_if!(3 <= idx, sv.is_since_cpp17());
+        assert_if!(4 <= idx, sv.is_since_cpp20());
+        assert_if!(5 <= idx, sv.is_since_cpp23());
+        assert_if!(6 <= idx, sv.is_since_cpp26());
 
         assert!(!sv.is_since_c89());
         assert!(!sv.is_since_c95());
@@ -89,6 +115,7 @@ fn std_version_since() {
         assert!(!sv.is_since_c11());
         assert!(!sv.is_since_c17());
         assert!(!sv.is_since_c23());
+        assert!(!sv.is_since_c2y());
     }
 }
 
@@ -96,4 +123,287 @@ fn std_version_since() {
 fn std_version_from_str() {
     let p = |s: &str| -> Result<StdVersion, _> { s.parse() };
     assert!(p("c11").is_ok());
+    assert_eq!(p("iso9899:1990"), Ok(C89));
+    assert_eq!(p("iso9899:199409"), Ok(C95));
+    assert_eq!(p("c1x"), Ok(C11));
+    assert_eq!(p("gnu11"), Ok(C11));
+    assert_eq!(p("c2y"), Ok(C2y));
+    assert_eq!(p("c++03"), Ok(Cpp98));
+    assert_eq!(p("gnu++1z"), Ok(Cpp17));
+    assert_eq!(p("c++2c"), Ok(Cpp26));
+    assert!(p("gnu95").is_err());
+    assert!(p("C11").is_err());
+}
+
+#[test]
+fn std_version_names_round_trip() {
+    for sv in StdVersion::iter() {
+        assert_eq!(sv.as_str().parse(), Ok(sv));
+        for &name in sv.aliases().iter().chain(sv.gnu_names()) {
+            assert_eq!(name.parse(), Ok(sv), "{name}");
+        }
+    }
+}
+
+#[test]
+fn std_dialect() {
+    let p = |s: &str| -> Result<StdDialect, _> { s.parse() };
+    let gnu = |sv| StdDialect::gnu(sv).unwrap();
+    assert_eq!(p("c99"), Ok(StdDialect::iso(C99)));
+    assert_eq!(p("gnu9x"), Ok(gnu(C99)));
+    assert_eq!(p("gnu++03"), Ok(gnu(Cpp98)));
+    assert_eq!(p("iso9899:2018"), Ok(StdDialect::iso(C17)));
+    assert_eq!(gnu(Cpp20).as_str(), "gnu++20");
+    assert_eq!(gnu(C2y).std_version(), C2y);
+    assert!(gnu(C2y).has_gnu_extensions());
+    assert_eq!(StdDialect::gnu(C95), None);
+
+    for dialect in StdDialect::iter() {
+        assert_eq!(p(dialect.as_str()), Ok(dialect));
+    }
+    assert_eq!(StdDialect::iter().count(), 27);
+}
+
+#[test]
+fn version_macros() {
+    assert_eq!(C89.version_value(), None);
+    assert_eq!(C11.version_macro(), "__STDC_VERSION__");
+    assert_eq!(C11.version_value(), Some(201112));
+    assert_eq!(Cpp98.version_macro(), "__cplusplus");
+    assert_eq!(Cpp98.version_value(), Some(199711));
+    for versions in [&C_VERSIONS[1..], &CPP_VERSIONS] {
+        for pair in versions.windows(2) {
+            assert!(pair[0].version_value() < pair[1].version_value());
+        }
+    }
+}
+
+#[test]
+fn feature_tables() {
+    for table in [FEATURE_TEST_MACROS, ATTRIBUTES] {
+        assert!(table.windows(2).all(|pair| pair[0].name() < pair[1].name()));
+        for feature in table {
+            let history = feature.history();
+            assert!(!history.is_empty(), "{}", feature.name());
+            assert!(
+                history.windows(2).all(|pair| {
+                    pair[0].0.as_language() != pair[1].0.as_language()
+                        || (pair[0].0 < pair[1].0 && pair[0].1 < pair[1].1)
+                }),
+                "{}",
+                feature.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn feature_values() {
+    let constexpr = feature_test_macro("__cpp_constexpr").unwrap();
+    assert_eq!(constexpr.value(Cpp98), None);
+    assert_eq!(constexpr.value(Cpp11), Some(200704));
+    assert_eq!(constexpr.value(Cpp17), Some(201603));
+    assert_eq!(constexpr.value(C23), None);
+    assert_eq!(
+        feature_test_macro("__cpp_concepts").unwrap().name(),
+        "__cpp_concepts"
+    );
+    assert_eq!(feature_test_macro("__cpp_lib_ranges"), None);
+
+    assert_eq!(feature_test_macros(C2y).count(), 0);
+    assert!(feature_test_macros(Cpp98)
+        .eq([("__cpp_exceptions", 199711), ("__cpp_rtti", 199711)]));
+    let concepts = ("__cpp_concepts", 202002);
+    assert!(feature_test_macros(Cpp20).any(|m| m == concepts));
+
+    let nodiscard = attribute("nodiscard").unwrap();
+    assert_eq!(nodiscard.value(C17), None);
+    assert_eq!(nodiscard.value(C23), Some(202003));
+    assert_eq!(nodiscard.value(Cpp17), Some(201603));
+    assert_eq!(nodiscard.value(Cpp26), Some(201907));
+    assert_eq!(attribute("__likely__"), attribute("likely"));
+    assert_eq!(attribute("_Noreturn").unwrap().value(Cpp11), None);
+    assert_eq!(attribute("gnu::packed"), None);
+}
+
+#[test]
+fn parse_errors() {
+    let err = |s: &str| s.parse::<StdVersion>().unwrap_err();
+    assert_eq!(err("c++27").suggestion(), Some("c++20"));
+    assert_eq!(err("cpp26").suggestion(), Some("c++26"));
+    assert_eq!(err("C11").suggestion(), Some("c11"));
+    assert_eq!(err("gnu95").suggestion(), Some("gnu90"));
+    assert_eq!(err("rust").suggestion(), None);
+    assert_eq!(err("").suggestion(), None);
+    assert_eq!(err("x").what(), "standard version");
+    assert_eq!(err("x").choices().len(), StdVersion::iter().count());
+    assert_eq!(err("c++27").input(), "c++27");
+    assert_eq!(
+        err("c++27").to_string(),
+        "unknown standard version 'c++27'; did you mean 'c++20'? expected one \
+         of: 'c89', 'c95', 'c99', 'c11', 'c17', 'c23', 'c2y', 'c++98', \
+         'c++11', 'c++14', 'c++17', 'c++20', 'c++23', 'c++26'"
+    );
+
+    let err = "cxx".parse::<Language>().unwrap_err();
+    assert_eq!(err.suggestion(), Some("c++"));
+    assert_eq!(
+        err.to_string(),
+        "unknown language 'cxx'; did you mean 'c++'? expected one of: 'c', \
+         'c++'"
+    );
+    let err = "rust".parse::<Language>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown language 'rust'; expected one of: 'c', 'c++'"
+    );
+    let err = "\"\n".parse::<Language>().unwrap_err();
+    assert_eq!(err.input(), "\"\n");
+    assert!(err.to_string().starts_with("unknown language '\\\"\\n';"));
+    let long = "\u{e9}".repeat(20);
+    let err = long.parse::<Language>().unwrap_err();
+    assert_eq!(err.input(), &long[..32]);
+    assert!(err.to_string().contains(&format!("'{}...';", &long[..32])));
+    assert_eq!(
+        "gnu++27".parse::<StdDialect>().unwrap_err().suggestion(),
+        Some("gnu++20")
+    );
+}
+
+#[test]
+fn display() {
+    assert_eq!(Language::Cpp.to_string(), "c++");
+    assert_eq!(Cpp20.to_string(), "c++20");
+    assert_eq!(StdDialect::iso(C99).to_string(), "c99");
+    assert_eq!(StdDialect::gnu(C99).unwrap().to_string(), "gnu99");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, Token};
+
+    serde_test::assert_tokens(&Language::C, &[Token::Str("c")]);
+    serde_test::assert_tokens(&Cpp17, &[Token::Str("c++17")]);
+    assert_de_tokens(&C99, &[Token::Str("iso9899:1999")]);
+    assert_de_tokens_error::<StdVersion>(
+        &[Token::Str("c++27")],
+        &err_string("c++27"),
+    );
+    let gnu17 = StdDialect::gnu(C17).unwrap();
+    serde_test::assert_tokens(&gnu17, &[Token::Str("gnu17")]);
+    assert_de_tokens(&gnu17, &[Token::Str("gnu18")]);
+}
+
+#[cfg(feature = "serde")]
+fn err_string(s: &str) -> alloc::string::String {
+    s.parse::<StdVersion>().unwrap_err().to_string()
+}
+
+#[test]
+fn target_presets() {
+    let x86_64 = TargetInfo::X86_64_LINUX_GNU;
+    assert_eq!(x86_64.data_model(), DataModel::Lp64);
+    assert_eq!((x86_64.long_width(), x86_64.pointer_width()), (64, 64));
+    assert!(x86_64.is_char_signed());
+    assert_eq!((x86_64.wchar_width(), x86_64.is_wchar_signed()), (32, true));
+
+    let aarch64 = TargetInfo::AARCH64_LINUX_GNU;
+    assert!(!aarch64.is_char_signed());
+    assert!(!aarch64.is_wchar_signed());
+
+    let i686 = TargetInfo::I686_LINUX_GNU;
+    assert_eq!(i686.data_model(), DataModel::Ilp32);
+    assert_eq!((i686.long_width(), i686.pointer_width()), (32, 32));
+    assert!(i686.is_char_signed());
+
+    let riscv64 = TargetInfo::RISCV64_LINUX_GNU;
+    assert!(!riscv64.is_char_signed());
+    assert!(riscv64.is_wchar_signed());
+
+    let arm = TargetInfo::ARM_NONE_EABI;
+    assert_eq!((arm.arch(), arm.os()), (Arch::Arm, Os::BareMetal));
+    assert_eq!(arm.data_model(), DataModel::Ilp32);
+    assert!(!arm.is_char_signed());
+    assert_eq!((arm.wchar_width(), arm.is_wchar_signed()), (32, false));
+
+    assert_eq!(TargetInfo::default(), x86_64);
+}
+
+#[test]
+fn target_triples() {
+    let p = |s: &str| -> Result<TargetInfo, _> { s.parse() };
+    assert_eq!(p("x86_64-linux-gnu"), Ok(TargetInfo::X86_64_LINUX_GNU));
+    assert_eq!(
+        p("x86_64-unknown-linux-gnu"),
+        Ok(TargetInfo::X86_64_LINUX_GNU)
+    );
+    assert_eq!(p("aarch64-linux-gnu"), Ok(TargetInfo::AARCH64_LINUX_GNU));
+    assert_eq!(p("i386-pc-linux-gnu"), Ok(TargetInfo::I686_LINUX_GNU));
+    assert_eq!(
+        p("riscv64gc-unknown-linux-gnu"),
+        Ok(TargetInfo::RISCV64_LINUX_GNU)
+    );
+    assert_eq!(p("arm-none-eabi"), Ok(TargetInfo::ARM_NONE_EABI));
+    assert_eq!(p("thumbv7em-none-eabihf"), Ok(TargetInfo::ARM_NONE_EABI));
+
+    let windows = p("x86_64-pc-windows-msvc").unwrap();
+    assert_eq!(windows.data_model(), DataModel::Llp64);
+    assert_eq!((windows.long_width(), windows.pointer_width()), (32, 64));
+    assert_eq!(
+        (windows.wchar_width(), windows.is_wchar_signed()),
+        (16, false)
+    );
+    assert_eq!(p("x86_64-w64-mingw32"), Ok(windows));
+
+    let darwin = p("arm64-apple-darwin").unwrap();
+    assert_eq!((darwin.arch(), darwin.os()), (Arch::Aarch64, Os::Darwin));
+    assert!(darwin.is_char_signed());
+    assert_eq!(p("aarch64_be-linux-gnu").unwrap().endian(), Endian::Big);
+
+    let err = p("x86_64-unknown-hurd-gnu").unwrap_err();
+    assert_eq!((err.what(), err.suggestion()), ("operating system", None));
+    let err = p("aarch46-linux-gnu").unwrap_err();
+    assert_eq!(
+        (err.what(), err.suggestion()),
+        ("architecture", Some("aarch64"))
+    );
+    let err = p("x86_64-unknown-linxu-gnu").unwrap_err();
+    assert_eq!(err.suggestion(), Some("linux"));
+    assert!(p("x86_64").is_err());
+    assert!(p("").is_err());
+}
+
+#[test]
+fn lang_options() {
+    let c89 = LangOptions::new(C89);
+    assert_eq!(c89.dialect(), StdDialect::iso(C89));
+    assert!(!c89.has_digraphs());
+    assert!(!c89.has_dollars_in_identifiers());
+    assert!(c89.with_digraphs(true).has_digraphs());
+    assert!(!c89.has_trigraphs());
+    assert!(c89.with_trigraphs(true).has_trigraphs());
+    assert!(!LangOptions::new(C23).with_trigraphs(true).has_trigraphs());
+    assert!(!LangOptions::new(Cpp17).with_trigraphs(true).has_trigraphs());
+
+    let gnu11 = LangOptions::from(StdDialect::gnu(C11).unwrap());
+    assert!(gnu11.has_gnu_extensions());
+    assert!(gnu11.has_dollars_in_identifiers());
+    assert_eq!(gnu11.dialect().as_str(), "gnu11");
+    assert!(!gnu11.with_char8_t(true).has_char8_t());
+
+    let cpp20 = LangOptions::from(Cpp20);
+    assert!(cpp20.has_char8_t() && cpp20.has_digraphs());
+    assert!(!cpp20.with_char8_t(false).has_char8_t());
+    assert!(!LangOptions::new(Cpp17).has_char8_t());
+    assert!(!cpp20.has_ms_extensions());
+    assert!(cpp20.with_ms_extensions(true).has_ms_extensions());
+    assert!(!cpp20.with_digraphs(false).has_digraphs());
+
+    // Every query is usable in const contexts
+    const CPP14: LangOptions = LangOptions::new(Cpp14).with_trigraphs(true);
+    const {
+        assert!(CPP14.has_trigraphs() && CPP14.has_digraphs());
+        assert!(!CPP14.has_char8_t() && CPP14.with_char8_t(true).has_char8_t());
+    }
 }
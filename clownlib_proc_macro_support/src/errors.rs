@@ -2,6 +2,7 @@ use quote::ToTokens;
 
 use std::fmt::Display;
 
+#[derive(Default)]
 pub struct ErrorsBuilder(Vec<syn::Error>);
 
 impl ErrorsBuilder {
@@ -23,6 +23,7 @@ pub trait OptionExt<T>: internal::Sealed<T> {
     /// let x: Option<&str> = None;
     /// assert_eq!(x.ok(), Err(()));
     /// ```
+    #[allow(clippy::result_unit_err)]
     fn ok(self) -> Result<T, ()>;
 
     /// Transforms the [`Option<T>`] into a [`Result<(), T>`], mapping
@@ -73,7 +74,6 @@ pub trait OptionExt<T>: internal::Sealed<T> {
     /// ```
     fn err_or<O>(self, ok: O) -> Result<O, T>;
 
-
     /// Transforms the [`Option<T>`] into a [`Result<O, T>`], mapping
     /// [`Some(v)`] to [`Err(v)`] and [`None`] to [`Ok(ok())`].
     ///
@@ -118,7 +118,7 @@ pub trait OptionExt<T>: internal::Sealed<T> {
 
 impl<T> OptionExt<T> for Option<T> {
     fn ok(self) -> Result<T, ()> {
-        self.map_or(Err(()), Ok)
+        self.ok_or(())
     }
 
     fn err(self) -> Result<(), T> {
@@ -162,7 +162,8 @@ mod tests {
 
     #[test]
     fn option_map_or_default() {
-        assert_eq!(Some(4).map_or_default(|n| vec![(); n]), vec![(); 4]);
-        assert_eq!(None.map_or_default(|n| vec![(); n]), vec![(); 0]);
+        let f = |n| vec![(); n];
+        assert_eq!(OptionExt::map_or_default(Some(4), f), vec![(); 4]);
+        assert_eq!(OptionExt::map_or_default(None, f), vec![(); 0]);
     }
 }